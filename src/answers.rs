@@ -0,0 +1,131 @@
+//! Known-correct answers, kept in `answers/<DAY>.toml` next to the inputs, as:
+//!
+//! ```toml
+//! part1 = "1234"
+//! part2 = "5678"
+//! ```
+//!
+//! They allow verifying the solutions offline, after reworking them.
+
+use std::{collections::HashMap, path::Path};
+
+use crate::{AppError, days::Entry, store::{FsInputStore, InputStore}};
+
+peg::parser! {
+
+	grammar parser() for str {
+
+		rule _ = [' '|'\t']*
+
+		rule comment() = "#" [_]*
+
+		rule part() -> i32 =
+			"part" n:$(['0'..='9']+) {? n.parse().or(Err("Expected part number")) }
+
+		rule answer() -> String =
+			"\"" a:$([^'"']*) "\"" { a.to_string() } /
+			a:$(['0'..='9'|'-']+) { a.to_string() }
+
+		pub rule line() -> Option<(i32,String)> =
+			_ p:part() _ "=" _ a:answer() _ comment()? { Some((p,a)) } /
+			_ comment()? { None }
+	}
+}
+
+/// The known answers of a day, by part
+#[derive(Debug,Default,PartialEq,Eq)]
+pub struct Answers(HashMap<i32,String>);
+
+impl Answers {
+
+	pub fn get(&self, part:i32) -> Option<&str> {
+		self.0.get(&part).map(String::as_str)
+	}
+
+	fn parse(input:&str) -> Result<Self,String> {
+		input.lines()
+			.enumerate()
+			.filter_map(|(n,l)| {
+				parser::line(l)
+					.map_err(|e| format!("line {}: {e}", n+1))
+					.transpose()
+			})
+			.collect::<Result<_,_>>()
+			.map(Self)
+	}
+
+	/// Loads the answers for a day from `answers/` under `root`, if there are any
+	pub fn load(root:&Path, day:i32) -> Result<Self,AppError> {
+
+		let path = root.join("answers").join(format!("{day}.toml"));
+
+		if !path.exists() {
+			return Ok(Self::default())
+		}
+
+		let content = std::fs::read_to_string(&path)
+			.map_err(AppError::io(&path))?;
+
+		Self::parse(&content)
+			.map_err(|e| AppError::BadConfiguration(format!("Malformed {}, {e}", path.display())))
+	}
+}
+
+/// The result of checking a solution against its known answer
+#[derive(Debug,PartialEq,Eq)]
+pub enum Verdict {
+	Correct(String),
+	Mismatch { expected: String, actual: String },
+	/// There is no known answer, or no input to solve
+	Skipped,
+}
+
+/// Solves the stored input of a registered solution,
+/// and compares the result with its known answer, kept alongside the inputs
+pub fn verify(store:&FsInputStore, entry:&Entry) -> Result<Verdict,AppError> {
+
+	let answers = Answers::load(store.root(), entry.day)?;
+
+	let Some(expected) = answers.get(entry.part) else {
+		return Ok(Verdict::Skipped)
+	};
+
+	let Some(input) = store.get(entry.day)? else {
+		return Ok(Verdict::Skipped)
+	};
+
+	let actual = (entry.solve)(&input)?;
+
+	if actual == expected {
+		Ok(Verdict::Correct(actual))
+	} else {
+		Ok(Verdict::Mismatch { expected: expected.to_string(), actual })
+	}
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	#[test]
+	fn test_parse() {
+
+		let input = indoc::indoc! {
+			r#"
+			# Day 1
+			part1 = "1234"
+
+			part2 = 5678 # unquoted
+			"#
+		};
+
+		let answers = Answers::parse(input).unwrap();
+
+		assert_eq!(answers.get(1),Some("1234"));
+		assert_eq!(answers.get(2),Some("5678"));
+		assert_eq!(answers.get(3),None);
+
+		assert!(Answers::parse("part1 = ").is_err());
+	}
+}
@@ -0,0 +1,155 @@
+//! Timing and allocation measurements of the registered solutions
+
+use std::{
+	alloc::{GlobalAlloc, Layout, System},
+	fmt::Write,
+	sync::atomic::{AtomicUsize, Ordering},
+	time::{Duration, Instant},
+};
+
+use itertools::Itertools;
+
+use crate::{AppError, days::Entry};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// A global allocator that counts allocations.
+/// Counts are only available to binaries that install it:
+///
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOC:CountingAlloc = CountingAlloc;
+/// ```
+pub struct CountingAlloc;
+
+unsafe impl GlobalAlloc for CountingAlloc {
+
+	unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+		ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+		ALLOCATED_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+		// SAFETY: Same contract as the caller's
+		unsafe { System.alloc(layout) }
+	}
+
+	unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+		// SAFETY: Same contract as the caller's
+		unsafe { System.dealloc(ptr, layout) }
+	}
+
+	unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+		ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+		ALLOCATED_BYTES.fetch_add(new_size, Ordering::Relaxed);
+		// SAFETY: Same contract as the caller's
+		unsafe { System.realloc(ptr, layout, new_size) }
+	}
+}
+
+fn allocation_counters() -> (usize,usize) {
+	(ALLOCATIONS.load(Ordering::Relaxed), ALLOCATED_BYTES.load(Ordering::Relaxed))
+}
+
+/// Measurements of a solution, over several runs
+#[derive(Debug,Clone)]
+pub struct Stats {
+	pub day: i32,
+	pub part: i32,
+	pub runs: usize,
+	pub min: Duration,
+	pub median: Duration,
+	pub max: Duration,
+	/// Allocations per run
+	pub allocations: usize,
+	/// Bytes allocated per run
+	pub bytes: usize,
+}
+
+/// Runs a solution `runs` times on the given input
+pub fn bench(entry:&Entry, input:&str, runs:usize) -> Result<Stats,AppError> {
+	measure(entry, runs, || (entry.solve)(input))
+}
+
+/// Runs a solution `runs` times on the given input, with the parameters given
+/// as `key=value` assignments, e.g. to compare methods
+pub fn bench_with(entry:&Entry, input:&str, spec:&str, runs:usize) -> Result<Stats,AppError> {
+	measure(entry, runs, || (entry.solve_with)(input, spec))
+}
+
+fn measure(entry:&Entry, runs:usize, solve:impl Fn() -> Result<String,AppError>) -> Result<Stats,AppError> {
+
+	assert!(runs > 0, "At least one run is needed");
+
+	let (allocs_before, bytes_before) = allocation_counters();
+
+	let times:Vec<Duration> = (0..runs)
+		.map(|_| {
+			let start = Instant::now();
+			solve().map(|_| start.elapsed())
+		})
+		.collect::<Result<Vec<_>,_>>()?
+		.into_iter()
+		.sorted_unstable()
+		.collect();
+
+	let (allocs_after, bytes_after) = allocation_counters();
+
+	Ok(Stats {
+		day: entry.day,
+		part: entry.part,
+		runs,
+		min: times[0],
+		median: times[runs / 2],
+		max: times[runs - 1],
+		allocations: (allocs_after - allocs_before) / runs,
+		bytes: (bytes_after - bytes_before) / runs,
+	})
+}
+
+/// Renders the measurements as a markdown table
+pub fn markdown(stats:&[Stats]) -> String {
+
+	let mut table = String::new();
+
+	table.push_str("| Day | Part | Runs | Min | Median | Max | Allocations | Bytes |\n");
+	table.push_str("|----:|-----:|-----:|----:|-------:|----:|------------:|------:|\n");
+
+	for s in stats {
+		let _ = writeln!(table,
+			"| {} | {} | {} | {:.2?} | {:.2?} | {:.2?} | {} | {} |",
+			s.day, s.part, s.runs, s.min, s.median, s.max, s.allocations, s.bytes
+		);
+	}
+
+	table
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	#[test]
+	fn test_bench() {
+
+		let entry = crate::days::find(1,1).unwrap();
+		let stats = bench(entry, "L68\nR18", 3).unwrap();
+
+		assert_eq!((stats.day,stats.part,stats.runs),(1,1,3));
+		assert!(stats.min <= stats.median && stats.median <= stats.max);
+
+		let table = markdown(&[stats]);
+
+		assert_eq!(table.lines().count(),3);
+		assert!(table.lines().last().unwrap().starts_with("| 1 | 1 | 3 |"));
+	}
+
+	#[test]
+	fn test_bench_with() {
+
+		let entry = crate::days::find(10,1).unwrap();
+		let input = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}";
+
+		assert!(bench_with(entry, input, "method=powerset", 2).is_ok());
+		assert!(bench_with(entry, input, "method=guess", 2).is_err());
+	}
+}
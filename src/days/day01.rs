@@ -18,6 +18,7 @@ impl Stride {
 		if matches!(self.dir,Dir::L) { -res } else { res }
 	}
 
+	#[cfg(test)]
 	fn into_raw(self) -> isize {
 		self.as_raw()
 	}
@@ -31,9 +32,40 @@ impl Stride {
 
 struct Dial(usize);
 
+/// The dial setup, by default that of the puzzle
+#[derive(Clone,Copy)]
+struct DialSetup {
+	start: usize
+}
+
+impl Default for DialSetup {
+	fn default() -> Self {
+		Self { start: 50 }
+	}
+}
+
+impl Params for DialSetup {
+	fn set(&mut self, key:&str, value:&str) -> Result<(),String> {
+		match key {
+			"start" => match param(key,value)? {
+				start @ 0..100 => self.start = start,
+				_ => return Err("The dial `start` should be in 0..100".into()),
+			},
+			_ => return Err(format!("Unknown parameter `{key}`, expected `start`")),
+		}
+		Ok(())
+	}
+}
+
+impl From<DialSetup> for Dial {
+	fn from(setup: DialSetup) -> Self {
+		Self(setup.start)
+	}
+}
+
 impl Default for Dial {
 	fn default() -> Self {
-		Self(50)
+		DialSetup::default().into()
 	}
 }
 
@@ -79,19 +111,22 @@ impl Solution for Part1 {
 
 	const DAY: i32 = 1;
 	const PART: Part = Part::Part1;
+	type Params = DialSetup;
 
-	fn solve(input:&str) -> impl Display {
+	fn solve_with(input:&str, setup:&DialSetup) -> Result<impl Display,ParseError> {
 
-		let mut dial = Dial::default();
-		let strides = parse(input,parser::stride);
+		let mut dial = Dial::from(*setup);
+		let strides = parse(input,parser::stride)?;
 
-		strides
+		let zeroes = strides
 			.map(|s| {
 				dial.turn(s);
 				dial.value()
 			})
 			.filter(Zero::is_zero)
-			.count()
+			.count();
+
+		Ok(zeroes)
 	}
 }
 
@@ -101,11 +136,12 @@ impl Solution for Part2 {
 
 	const DAY: i32 = 1;
 	const PART: Part = Part::Part2;
+	type Params = DialSetup;
 
-	fn solve(input:&str) -> impl Display {
+	fn solve_with(input:&str, setup:&DialSetup) -> Result<impl Display,ParseError> {
 
-		let mut dial = Dial::default();
-		let strides = parse(input,parser::stride);
+		let mut dial = Dial::from(*setup);
+		let strides = parse(input,parser::stride)?;
 
 		let mut zero_crossed:usize = 0;
 
@@ -146,10 +182,12 @@ impl Solution for Part2 {
 			}
 		}
 
-		zero_crossed
+		Ok(zero_crossed)
 	}
 }
 
+register! { Part1, Part2 }
+
 #[cfg(test)]
 mod test {
 
@@ -172,7 +210,7 @@ mod test {
 	#[test]
 	fn test_parse() {
 
-		let turns = parse(EXAMPLE_INPUT,parser::stride);
+		let turns = parse(EXAMPLE_INPUT,parser::stride).unwrap();
 
 		let expected = vec![ -68, -30, 48, -5, 60, -55, -1,-99, 14, -82 ];
 		let actual:Vec<isize> = turns.map(Stride::into_raw).collect();
@@ -185,7 +223,7 @@ mod test {
 
 		let mut dial = Dial::default();
 
-		let strides = parse(EXAMPLE_INPUT,parser::stride);
+		let strides = parse(EXAMPLE_INPUT,parser::stride).unwrap();
 
 		let expected = vec![ 82, 52, 0, 95, 55, 0, 99, 0, 14, 32 ];
 
@@ -198,17 +236,26 @@ mod test {
 	}
 
 	#[test]
-	fn test_example() {
+	fn test_parse_error() {
 
-		let expected = "3";
-		let actual = Part1::solve(EXAMPLE_INPUT).to_string();
+		let input = "L68\nL30\nX48\n";
+
+		let actual = parse(input,parser::stride).err().unwrap();
+		let expected = ParseError::new(3, 1, "X48", "['L'|'R']");
 
 		assert_eq!(actual,expected);
 
-		let expected = "6";
-		let actual = Part2::solve(EXAMPLE_INPUT).to_string();
+		let actual = Part1::try_solve(input).err().unwrap();
 
-		assert_eq!(actual,expected);
+		assert!(matches!(actual, AppError::Parse(ParseError { day: Some(1), line: 3, .. })));
+	}
+
+	// EXAMPLES
+
+	examples! {
+		part1: Part1 => EXAMPLE_INPUT => "3",
+		part2: Part2 => EXAMPLE_INPUT => "6",
+		part1_start_at_68: Part1(DialSetup { start: 68 }) => EXAMPLE_INPUT => "1",
 	}
 
 	// SOLUTIONS
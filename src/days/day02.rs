@@ -19,6 +19,12 @@ peg::parser! {
 	}
 }
 
+// The input is a single line of ranges
+fn parse_ranges(input:&str) -> Result<Vec<RangeInclusive<usize>>,ParseError> {
+	let line = input.lines().next().unwrap_or_default();
+	parse_line(0, line, parser::ranges)
+}
+
 fn has_eq_chunks_of(str:&str, chunk_size:usize) -> bool {
 
 	let len = str.len();
@@ -52,13 +58,13 @@ impl Solution for Part1 {
 
 	const DAY: i32 = 2;
 	const PART: Part = Part::Part1;
+	type Params = ();
 
-
-	fn solve(input:&str) -> impl Display {
+	fn solve_with(input:&str, _:&()) -> Result<impl Display,ParseError> {
 
 		use num::Integer;
 
-		let ranges = parse(input,parser::ranges).next().unwrap();
+		let ranges = parse_ranges(input)?;
 
 		let mut total:usize = 0;
 
@@ -77,7 +83,7 @@ impl Solution for Part1 {
 			}
 		}
 
-		total
+		Ok(total)
 	}
 }
 
@@ -88,12 +94,11 @@ impl Solution for Part2 {
 
 	const DAY: i32 = 2;
 	const PART: Part = Part::Part2;
+	type Params = ();
 
+	fn solve_with(input:&str, _:&()) -> Result<impl Display,ParseError> {
 
-	fn solve(input:&str) -> impl Display {
-		
-
-		let ranges = parse(input,parser::ranges).next().unwrap();
+		let ranges = parse_ranges(input)?;
 
 		let mut total:usize = 0;
 
@@ -114,10 +119,12 @@ impl Solution for Part2 {
 			}
 		}
 
-		total
+		Ok(total)
 	}
 }
 
+register! { Part1, Part2 }
+
 #[cfg(test)]
 mod test {
 
@@ -125,26 +132,10 @@ mod test {
 
 	const EXAMPLE_INPUT:&str = r#"11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124"#;
 
-	#[test]
-	fn test_examples() {
-
-		// part 1
-
-		let expected = "1227775554";
-		let actual = Part1::solve(EXAMPLE_INPUT).to_string();
-		assert_eq!(actual,expected);
-
-		// part 2
-
-		let expected = "4174379265";
-		let actual = Part2::solve(EXAMPLE_INPUT).to_string();
-		assert_eq!(actual,expected);
-	}
-
 	#[test]
 	fn test_parse() {
 
-		let actual:Vec<RangeInclusive<usize>> = parse(EXAMPLE_INPUT,parser::ranges).take(1).next().unwrap();
+		let actual:Vec<RangeInclusive<usize>> = parse_ranges(EXAMPLE_INPUT).unwrap();
 
 		let expected:Vec<RangeInclusive<usize>> = vec![
 			11..=22, 95..=115, 998..=1012, 1188511880..=1188511890, 222220..=222224,
@@ -155,6 +146,13 @@ mod test {
 		assert_eq!(actual,expected);
 	}
 
+	// EXAMPLES
+
+	examples! {
+		part1: Part1 => EXAMPLE_INPUT => "1227775554",
+		part2: Part2 => EXAMPLE_INPUT => "4174379265",
+	}
+
 	// SOLUTIONS
 
 	submit! { Part1 }
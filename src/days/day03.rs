@@ -8,6 +8,7 @@ use derive_more::{Deref, From};
 struct BatteryBank(Vec<u8>);
 
 impl BatteryBank {
+	#[cfg(test)]
 	pub fn iter<'a>(&'a self) -> impl 'a + Iterator<Item=u8> {
 		self.0.iter().copied()
 	}
@@ -26,14 +27,8 @@ fn first_max(slice:&[u8])->(usize,u8) {
 
 impl BatteryBank {
 
-	fn max_pair(&self) -> (u8,u8) {
-
-		let nums = self.max_of(2);
-
-		(nums[0],nums[1])
-	}
-
-	fn max_of(&self,digits:usize) -> Vec<u8> {
+	/// The largest digits in order, if the bank has enough of them
+	fn max_of(&self,digits:usize) -> Option<Vec<u8>> {
 
 		let mut res:Vec<u8> = Vec::with_capacity(digits);
 
@@ -42,7 +37,7 @@ impl BatteryBank {
 		// end: starts with a reserve for N-1 digits,
 		// increase limit as less values remain
 
-		let mut range = 0..=self.len()-digits;
+		let mut range = 0..=self.len().checked_sub(digits)?;
 
 		while res.len() < digits {
 			let (at,val) = first_max(&self[range.clone()]);
@@ -50,7 +45,7 @@ impl BatteryBank {
 			range = range.start()+at+1..=range.end()+1
 		}
 
-		res
+		Some(res)
 	}
 
 }
@@ -65,21 +60,59 @@ peg::parser! {
 	}
 }
 
+/// The largest joltage of each bank, turning on `count` batteries
+fn joltages(input:&str, count:usize) -> Result<Vec<usize>,ParseError> {
+	input.lines()
+		.enumerate()
+		.map(|(n,line)| {
+			let bank = parse_line(n, line, parser::bank)?;
+			let nums = bank.max_of(count)
+				.ok_or_else(|| ParseError::new(n+1, line.len()+1, line, format!("at least {count} batteries")))?;
+			// assemble digits
+			Ok(nums.into_iter().fold(0, |joltage,n| joltage*10 + n as usize))
+		})
+		.collect()
+}
+
 struct Part1;
 
 impl Solution for Part1 {
 
 	const DAY: i32 = 3;
 	const PART: Part = Part::Part1;
+	type Params = ();
 
-	fn solve(input:&str) -> impl Display {
+	fn solve_with(input:&str, _:&()) -> Result<impl Display,ParseError> {
 
-		let banks = parse(input,parser::bank);
+		Ok(joltages(input, 2)?.into_iter().sum::<usize>())
+	}
+}
+
+/// How many batteries are turned on in each bank
+struct Batteries {
+	count: usize
+}
+
+impl Default for Batteries {
+	fn default() -> Self {
+		Self { count: 12 }
+	}
+}
 
-		banks.map(|b| {
-			let (fst,snd) = b.max_pair();
-			(fst*10+snd) as usize
-		}).sum::<usize>()
+impl Params for Batteries {
+	fn set(&mut self, key:&str, value:&str) -> Result<(),String> {
+		match key {
+			"count" => match param(key,value)? {
+				0 => return Err("At least one battery should be turned on".into()),
+				// The joltage should fit in the answer
+				count if count > usize::MAX.ilog10() as usize => {
+					return Err(format!("At most {} batteries can be turned on", usize::MAX.ilog10()))
+				},
+				count => self.count = count,
+			},
+			_ => return Err(format!("Unknown parameter `{key}`, expected `count`")),
+		}
+		Ok(())
 	}
 }
 
@@ -89,25 +122,16 @@ impl Solution for Part2 {
 
 	const DAY: i32 = 3;
 	const PART: Part = Part::Part2;
+	type Params = Batteries;
 
-	fn solve(input:&str) -> impl Display {
-
-		let banks = parse(input,parser::bank);
-
-		banks.map(|b| {
+	fn solve_with(input:&str, batteries:&Batteries) -> Result<impl Display,ParseError> {
 
-			let nums = b.max_of(12);
-			// assemble digits
-			nums.iter()
-				.map(u8::to_string)
-				.join("")
-				.parse::<usize>()
-				.unwrap()
-
-		}).sum::<usize>()
+		Ok(joltages(input, batteries.count)?.into_iter().sum::<usize>())
 	}
 }
 
+register! { Part1, Part2 }
+
 #[cfg(test)]
 mod test {
 
@@ -127,7 +151,7 @@ mod test {
 
 		use itertools::assert_equal;
 
-		let pack:Vec<BatteryBank> = parse(EXAMPLE_INPUT,parser::bank).collect();
+		let pack:Vec<BatteryBank> = parse(EXAMPLE_INPUT,parser::bank).unwrap().collect();
 
 		let actual = pack.first().unwrap().iter();
 		let expected = vec![9,8,7,6,5,4,3,2,1,1,1,1,1,1,1];
@@ -145,28 +169,43 @@ mod test {
 
 		// part 1
 
-		let mut banks = parse(EXAMPLE_INPUT,parser::bank);
+		let mut banks = parse(EXAMPLE_INPUT,parser::bank).unwrap();
 
-		assert_eq!((9,8),banks.next().unwrap().max_pair());
-		assert_eq!((8,9),banks.next().unwrap().max_pair());
-		assert_eq!((7,8),banks.next().unwrap().max_pair());
-		assert_eq!((9,2),banks.next().unwrap().max_pair());
+		assert_eq!(Some(vec![9,8]),banks.next().unwrap().max_of(2));
+		assert_eq!(Some(vec![8,9]),banks.next().unwrap().max_of(2));
+		assert_eq!(Some(vec![7,8]),banks.next().unwrap().max_of(2));
+		assert_eq!(Some(vec![9,2]),banks.next().unwrap().max_of(2));
 
 		// part 2
 
-		let mut banks = parse(EXAMPLE_INPUT,parser::bank);
+		let mut banks = parse(EXAMPLE_INPUT,parser::bank).unwrap();
 
-		assert_eq!(*parser::bank("987654321111").unwrap(),banks.next().unwrap().max_of(12));
-		assert_eq!(*parser::bank("811111111119").unwrap(),banks.next().unwrap().max_of(12));
-		assert_eq!(*parser::bank("434234234278").unwrap(),banks.next().unwrap().max_of(12));
-		assert_eq!(*parser::bank("888911112111").unwrap(),banks.next().unwrap().max_of(12));
+		assert_eq!(Some(parser::bank("987654321111").unwrap().0),banks.next().unwrap().max_of(12));
+		assert_eq!(Some(parser::bank("811111111119").unwrap().0),banks.next().unwrap().max_of(12));
+		assert_eq!(Some(parser::bank("434234234278").unwrap().0),banks.next().unwrap().max_of(12));
+		assert_eq!(Some(parser::bank("888911112111").unwrap().0),banks.next().unwrap().max_of(12));
+
+		assert_eq!(parser::bank("12345").unwrap().max_of(12), None);
 	}
 
 	#[test]
-	fn test_part1_example() {
-		let actual = Part1::solve(EXAMPLE_INPUT).to_string();
-		let expected = "357";
-		assert_eq!(actual,expected);
+	fn test_short_bank() {
+
+		let error = Part2::solve("987654321111\n12345\n").err().unwrap();
+		assert_eq!((error.line, error.column), (2, 6));
+		assert_eq!(error.expected, "at least 12 batteries");
+
+		assert!(Part1::solve("9").is_err());
+		assert!(Batteries::parse("count=19").is_ok());
+		assert!(Batteries::parse("count=20").is_err());
+	}
+
+	// EXAMPLES
+
+	examples! {
+		part1: Part1 => EXAMPLE_INPUT => "357",
+		part2: Part2 => EXAMPLE_INPUT => "3121910778619",
+		part2_two_batteries: Part2(Batteries { count: 2 }) => EXAMPLE_INPUT => "357",
 	}
 
 	// SOLUTIONS
@@ -1,148 +1,68 @@
 //! https://adventofcode.com/2025/day/4
 
-use std::ops::{Index, IndexMut, RangeInclusive};
 use derive_more::IsVariant;
-use num::integer::div_rem;
 
-use super::*;
+use super::{*, Grid as GenericGrid};
 
-#[derive(Debug,Clone,Copy,PartialEq,Eq,IsVariant)]
+#[derive(Debug,Clone,Copy,PartialEq,Eq,Hash,IsVariant)]
 enum Cell {
 	Empty,
 	Roll
 }
 
-trait Swap {
-	fn swap(self) -> Self;
-}
+impl TryFrom<u8> for Cell {
+	type Error = ();
 
-impl<T> Swap for (T,T) {
-	fn swap(self) -> Self {
-		(self.1,self.0)
+	fn try_from(value: u8) -> Result<Self, Self::Error> {
+		match value {
+			b'@' => Ok(Self::Roll),
+			b'.' => Ok(Self::Empty),
+			_ => Err(())
+		}
 	}
 }
 
-struct Grid {
-	size: (usize,usize),
-	data: Vec<Cell>
-}
-
-impl Index<usize> for Grid {
-	type Output = Cell;
-
-	fn index(&self, index: usize) -> &Self::Output {
-		&self.data[index]
+impl From<Cell> for u8 {
+	fn from(value: Cell) -> Self {
+		match value {
+			Cell::Roll  => b'@',
+			Cell::Empty => b'.',
+		}
 	}
 }
 
-impl IndexMut<usize> for Grid {
-	fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-		&mut self.data[index]
-	}
-}
+type Grid = GenericGrid<Cell>;
 
 impl Grid {
 
-	fn new(input: &str) -> Self {
-		let mut lines = parse(input,parser::grid_line);
-		let mut data = lines.next().unwrap();
-		let width = data.len();
-		let mut height = 1;
-		for mut l in lines {
-			height += 1;
-			data.append(&mut l);
-		}
-		Self { size:(width,height), data }
-	}
-
-	#[inline]
-	fn last(&self)->usize {
-		self.data.len()-1
-	}
-
-	#[allow(unused)]
-	fn coordinate(&self,i:usize)->(usize,usize) {
-		div_rem(i, self.size.0).swap()
-	}
-
-	fn range(&self) -> RangeInclusive<usize> {
-		0..=self.last()
-	}
-
-	/// Returns the upper and lower bound for the row
-	/// that contains index i
-	fn row(&self, i:usize) -> RangeInclusive<usize> {
-		let (width,_) = self.size;
-		let offset_x = i % width;
-		let left = i-offset_x;
-		left..=(left+width-1)
-	}
-
-	fn roll_locations(&self) -> impl Iterator<Item=usize> {
-		self.range().filter(|&i| self[i].is_roll())
-	}
-
-	fn row_neighbours(&self, i:usize) -> impl Iterator<Item=usize> {
-		let row_bounds = self.row(i);
-		let (min,max) = (*row_bounds.start(), *row_bounds.end());
-		[
-			i.saturating_sub(1).clamp(min,max),
-			i,
-			i.saturating_add(1).clamp(min,max)
-		].into_iter()
+	fn roll_locations(&self) -> impl Iterator<Item=Location> {
+		self.enumerate()
+			.filter(|(_,cell)| cell.is_roll())
+			.map(|(xy,_)| xy.into())
 	}
 
-	fn neighbours(&self,i:usize) -> impl Iterator<Item=usize> {
-		let (width,_) = self.size;
-
-
-		let up   = i.checked_sub(width).unwrap_or(i);
-		let down = {
-			let v = i + width;
-			if v <= self.last() { v } else { i }
-		};
-
-		itertools::chain!(
-				self.row_neighbours(up),
-				self.row_neighbours(i),
-				self.row_neighbours(down)
-			)
-			// rows can be repeated in first and last rows
-			.unique()
-			// exclude 'i'
-			.filter(move |&v| v != i)
-	}
-
-	fn has_reachable_roll_at(&self,i:usize) -> bool {
-		self.neighbours(i).filter(|&i| self[i].is_roll()).count() < 4
+	fn has_reachable_roll_at(&self,loc:Location) -> bool {
+		self.neighbours8(loc).filter(|&n| self[n].is_roll()).count() < 4
 	}
 }
 
-peg::parser! {
-
-	grammar parser() for str {
-		rule roll()  -> Cell = "@" { Cell::Roll }
-		rule empty() -> Cell = "." { Cell::Empty }
-		pub rule grid_line() -> Vec<Cell> =
-			cs:((roll()/empty())+) { cs }
-	}
-
-}
-
 struct Part1;
 
 impl Solution for Part1 {
 
 	const DAY: i32 = 4;
 	const PART: Part = Part::Part1;
+	type Params = ();
 
-	fn solve(input:&str) -> impl Display {
+	fn solve_with(input:&str, _:&()) -> Result<impl Display,ParseError> {
 
-		let grid = Grid::new(input);
+		let grid = input.parse::<Grid>()?;
 
-		grid.roll_locations().filter(|&i| {
-			grid.has_reachable_roll_at(i)
-		}).count()
+		let reachable = grid.roll_locations().filter(|&loc| {
+			grid.has_reachable_roll_at(loc)
+		}).count();
+
+		Ok(reachable)
 	}
 }
 struct Part2;
@@ -151,30 +71,28 @@ impl Solution for Part2 {
 
 	const DAY: i32 = 4;
 	const PART: Part = Part::Part2;
+	type Params = ();
 
-	fn solve(input:&str) -> impl Display {
-
-		let mut grid = Grid::new(input);
-
-		let mut total_removed = 0;
+	fn solve_with(input:&str, _:&()) -> Result<impl Display,ParseError> {
 
-		// TODO: keep state for known roll locations
+		let grid = input.parse::<Grid>()?;
 
-		loop {
+		// Accessible rolls are removed a round at a time,
+		// and only the rolls around removed ones can become accessible
+		let remove_accessible = |grid:&Grid, loc:Location| match grid[loc] {
+			Cell::Roll if grid.has_reachable_roll_at(loc) => Cell::Empty,
+			cell => cell,
+		};
 
-			let to_be_removed = grid.roll_locations()
-				.filter(|&i| grid.has_reachable_roll_at(i))
-				.collect_vec();
+		let mut automaton = Automaton::new(grid, Connectivity::Eight);
+		automaton.run(remove_accessible, usize::MAX);
 
-			if to_be_removed.is_empty() {
-				break total_removed;
-			} else {
-				to_be_removed.iter().for_each(|&i| { grid[i] = Cell::Empty });
-				total_removed += to_be_removed.len();
-			}
-		}
+		Ok(automaton.changes().iter().sum::<usize>())
 	}
 }
+
+register! { Part1, Part2 }
+
 #[cfg(test)]
 mod test {
 	use super::*;
@@ -194,18 +112,11 @@ mod test {
 		"#
 	};
 
-	#[test]
-	fn test_example() {
-
-		let actual = Part1::solve(EXAMPLE_INPUT).to_string();
-		let expected = "13";
-
-		assert_eq!(actual,expected);
-
-		let actual = Part2::solve(EXAMPLE_INPUT).to_string();
-		let expected = "43";
+	// EXAMPLES
 
-		assert_eq!(actual,expected)
+	examples! {
+		part1: Part1 => EXAMPLE_INPUT => "13",
+		part2: Part2 => EXAMPLE_INPUT => "43",
 	}
 
 	// SOLUTIONS
@@ -16,18 +16,21 @@ peg::parser! {
 	}
 }
 
-fn parse(input:&str) -> (Vec<RangeInclusive<usize>>,impl Iterator<Item=usize>) {
+fn parse(input:&str) -> Result<(Vec<RangeInclusive<usize>>,impl Iterator<Item=usize>),ParseError> {
 
-	let mut lines = input.lines();
+	let mut lines = input.lines().enumerate();
 
 	let ranges = lines
-		.take_while_ref(|line| !line.is_empty())
-		.map(|line| parser::range(line).unwrap())
-		.collect_vec();
+		.take_while_ref(|(_,line)| !line.is_empty())
+		.map(|(n,line)| parse_line(n, line, parser::range))
+		.try_collect()?;
 
-	let ids = lines.skip(1).map(|line| parser::number(line).unwrap());
+	let ids:Vec<usize> = lines
+		.skip(1)
+		.map(|(n,line)| parse_line(n, line, parser::number))
+		.try_collect()?;
 
-	(ranges,ids)
+	Ok((ranges,ids.into_iter()))
 }
 
 struct Part1;
@@ -36,14 +39,17 @@ impl Solution for Part1 {
 
 	const DAY: i32 = 5;
 	const PART: Part = Part::Part1;
+	type Params = ();
 
-	fn solve(input:&str) -> impl Display {
+	fn solve_with(input:&str, _:&()) -> Result<impl Display,ParseError> {
 
-		let (ranges,ids) = parse(input);
+		let (ranges,ids) = parse(input)?;
 
-		ids.filter(|id| {
+		let fresh = ids.filter(|id| {
 			ranges.iter().any(|r| r.contains(id))
-		}).count()
+		}).count();
+
+		Ok(fresh)
 	}
 }
 
@@ -102,10 +108,11 @@ impl Solution for Part2 {
 
 	const DAY: i32 = 5;
 	const PART: Part = Part::Part2;
+	type Params = ();
 
-	fn solve(input:&str) -> impl Display {
+	fn solve_with(input:&str, _:&()) -> Result<impl Display,ParseError> {
 
-		let (ranges,_) = parse(input);
+		let (ranges,_) = parse(input)?;
 
 		let ranges = ranges
 			.into_iter()
@@ -125,7 +132,7 @@ impl Solution for Part2 {
 			}
 		}
 
-		ranges.fold(State::default(), |mut st,right| {
+		let fresh = ranges.fold(State::default(), |mut st,right| {
 			if let Some(left) = st.ran {
 				if let Some(merged) = left.unchecked_try_merge(&right) {
 					st.ran = Some(merged)
@@ -141,10 +148,14 @@ impl Solution for Part2 {
 
 			st
 
-		}).into_count()
+		}).into_count();
+
+		Ok(fresh)
 	}
 }
 
+register! { Part1, Part2 }
+
 #[cfg(test)]
 mod test {
 
@@ -169,7 +180,7 @@ mod test {
 	#[test]
 	fn test_parse() {
 
-		let (ranges,ids) = parse(EXAMPLE_INPUT);
+		let (ranges,ids) = parse(EXAMPLE_INPUT).unwrap();
 
 		let expected_ranges = vec![
 			3..=5,
@@ -186,16 +197,11 @@ mod test {
 		assert_eq!(ids,expected_ids);
 	}
 
-	#[test]
-	fn test_example() {
-
-		let actual = Part1::solve(EXAMPLE_INPUT).to_string();
-		let expected = "3";
-		assert_eq!(actual,expected);
+	// EXAMPLES
 
-		let actual = Part2::solve(EXAMPLE_INPUT).to_string();
-		let expected = "14";
-		assert_eq!(actual,expected);
+	examples! {
+		part1: Part1 => EXAMPLE_INPUT => "3",
+		part2: Part2 => EXAMPLE_INPUT => "14",
 	}
 
 	// SOLUTIONS
@@ -57,30 +57,40 @@ peg::parser! {
 	}
 }
 
-fn parse(input:&str) -> (Vec<Vec<usize>>, Vec<Op>) {
+fn parse(input:&str) -> Result<(Vec<Vec<usize>>, Vec<Op>),ParseError> {
 
-	let mut iter = input.lines().peekable();
+	let mut iter = input.lines().enumerate().peekable();
 
 	let mut all_opnds:Vec<Vec<usize>> = Default::default();
 	let mut operators:Vec<Op> = Default::default();
 
 	loop {
 
-		let Some(line) = iter.next() else {
-			assert!(!operators.is_empty());
-			break (all_opnds,operators)
+		let Some((n,line)) = iter.next() else {
+			if operators.is_empty() {
+				break Err(ParseError::new(1, 1, "", "an operators line"))
+			}
+			if all_opnds.is_empty() {
+				break Err(ParseError::new(1, 1, input.lines().next().unwrap_or_default(), "an operands line before the operators"))
+			}
+			// Every operator applies to a column of operands
+			if let Some((n,opnds)) = all_opnds.iter().find_position(|opnds| opnds.len() != operators.len()) {
+				let line = input.lines().nth(n).unwrap_or_default();
+				break Err(ParseError::new(n+1, line.len()+1, line, format!("{} operands, one per operator, not {}", operators.len(), opnds.len())))
+			}
+			break Ok((all_opnds,operators))
 		};
 
 		if iter.peek().is_some() {
 
 			// Parse operands line
-			let operands = parser::operands(line).unwrap();
+			let operands = parse_line(n, line, parser::operands)?;
 			all_opnds.push(operands);
 
 		} else {
 
 			// Last line, parse operators
-			operators = parser::operators(line).unwrap();
+			operators = parse_line(n, line, parser::operators)?;
 		}
 	}
 }
@@ -102,27 +112,21 @@ impl Solution for Part1 {
 
 	const DAY: i32 = 6;
 	const PART: Part = Part::Part1;
+	type Params = ();
 
-	fn solve(input:&str) -> impl Display {
+	fn solve_with(input:&str, _:&()) -> Result<impl Display,ParseError> {
 
-		let (operands,operators) = parse(input);
+		let (operands,operators) = parse(input)?;
 
-		compute_cols(operands, operators).iter().sum::<usize>()
+		Ok(compute_cols(operands, operators).iter().sum::<usize>())
 	}
 }
 
-fn transposed_chunks(lines:Vec<&str>)-> Vec<String> {
-
-	let cols = lines[0].len();
-	let rows = lines.len();
-
-	let coords = (0..cols).rev().cartesian_product(0..rows);
-
-	coords
-		.map(|(x,y)| unsafe { lines[y].get_unchecked(x..=x) })
-		.chunks(rows)
-		.into_iter()
-		.map(|c| c.collect_vec().join(""))
+/// The columns of the input, right to left, each read top to bottom
+fn transposed_chunks(grid:&Grid<u8>)-> Vec<String> {
+	grid.rotate_left()
+		.rows()
+		.map(|row| String::from_utf8_lossy(row).into_owned())
 		.collect_vec()
 }
 
@@ -132,16 +136,25 @@ impl Solution for Part2 {
 
 	const DAY: i32 = 6;
 	const PART: Part = Part::Part2;
+	type Params = ();
 
-	fn solve(input:&str) -> impl Display {
+	fn solve_with(input:&str, _:&()) -> Result<impl Display,ParseError> {
 
 		let lines = input.lines().collect_vec();
-
-		let cols = transposed_chunks(lines).into_iter()
-			.map(|ref c| parser::col(c).expect("Column should parse into a Col instance"))
-			.collect_vec().into_iter();
-
-		cols
+		let grid = Grid::from_str_with(input, Some)?;
+		let width = grid.size.width;
+
+		let cols:Vec<Col> = transposed_chunks(&grid).into_iter()
+			.enumerate()
+			.map(|(i,c)| parser::col(&c).map_err(|e| {
+				// Locate the error in the original, untransposed, input
+				let y = (e.location.column - 1).min(lines.len() - 1);
+				let x = width - 1 - i;
+				ParseError::new(y+1, x+1, lines[y], e.expected.to_string())
+			}))
+			.try_collect()?;
+
+		let total = cols.into_iter()
 			.chunk_by(|col| matches!(col,Col::Sep))
 			.into_iter()
 			.map(|(is_sep,group)| {
@@ -164,10 +177,14 @@ impl Solution for Part2 {
 
 				opnds.into_iter().reduce(|a,b| optr.compute(a,b)).unwrap()
 			})
-			.sum::<usize>()
+			.sum::<usize>();
+
+		Ok(total)
 	}
 }
 
+register! { Part1, Part2 }
+
 #[cfg(test)]
 mod test {
 
@@ -183,19 +200,24 @@ mod test {
 	#[test]
 	fn test_parser() {
 
-		let (operands,operators) = parse(EXAMPLE_INPUT);
+		let (operands,operators) = parse(EXAMPLE_INPUT).unwrap();
 
 		assert_eq!(operands[0], vec![123, 328,  51,  64]);
 		assert_eq!(operands[1], vec![ 45,  64, 387,  23]);
 		assert_eq!(operands[2], vec![  6,  98, 215, 314]);
 
 		assert_eq!(operators, vec![Op::Prod, Op::Sum, Op::Prod, Op::Sum]);
+
+		assert!(parse("+\n").is_err());
+
+		let actual = parse("1 2\n3\n+ *\n").err().unwrap();
+		assert_eq!((actual.line,actual.column),(2,2));
 	}
 
 	#[test]
 	fn test_compute() {
 
-		let (operands,operators) = parse(EXAMPLE_INPUT);
+		let (operands,operators) = parse(EXAMPLE_INPUT).unwrap();
 		let mut computed = compute_cols(operands, operators).into_iter();
 
 		assert_eq!(computed.next().unwrap(),33210);
@@ -207,8 +229,8 @@ mod test {
 	#[test]
 	fn test_transpose() {
 
-		let lines = EXAMPLE_INPUT.lines().collect_vec();
-		let mut cols = transposed_chunks(lines).into_iter();
+		let grid = Grid::from_str_with(EXAMPLE_INPUT, Some).unwrap();
+		let mut cols = transposed_chunks(&grid).into_iter();
 
 		assert_eq!(cols.next().unwrap(), "  4 ");
 		assert_eq!(cols.next().unwrap(), "431 ");
@@ -237,19 +259,11 @@ mod test {
 		assert_eq!(col("623+").unwrap(),Col::OpndOptr(623,Op::Sum));
 	}
 
-	#[test]
-	fn test_example() {
-
-		let actual = Part1::solve(EXAMPLE_INPUT).to_string();
-		let expected = "4277556";
-
-		assert_eq!(actual,expected);
-
-		let actual = Part2::solve(EXAMPLE_INPUT).to_string();
-		let expected = "3263827";
-
-		assert_eq!(actual,expected);
+	// EXAMPLES
 
+	examples! {
+		part1: Part1 => EXAMPLE_INPUT => "4277556",
+		part2: Part2 => EXAMPLE_INPUT => "3263827",
 	}
 
 	// SOLUTIONS
@@ -38,6 +38,17 @@ impl TryFrom<u8> for Item {
 	}
 }
 
+impl Item {
+	fn colour(&self) -> Rgb {
+		match self {
+			Item::Empty     => Rgb::from_hex(0x0f0f23),
+			Item::Source    => Rgb::from_hex(0xffff66),
+			Item::Splitter  => Rgb::from_hex(0xcccccc),
+			Item::Beam      => Rgb::from_hex(0x00cc00),
+		}
+	}
+}
+
 impl From<Item> for u8 {
 	fn from(value: Item) -> Self {
 		match value {
@@ -49,35 +60,36 @@ impl From<Item> for u8 {
 	}
 }
 
-fn parse(input:&str) -> Grid {
+/// Parses the manifold, which should have its start point in the top row,
+/// and no splitter on the left or right edge for the beams to stay within
+fn parse(input:&str) -> Result<Grid,ParseError> {
 
-	let parse_line = |l:&str| -> Vec<Item> {
-		l.as_bytes().iter().map(|&b| b.try_into().unwrap()).collect_vec()
-	};
+	let grid:Grid = input.parse()?;
+	let line = |y:usize| input.lines().nth(y).unwrap_or_default();
 
-	let mut items = input.lines().map(parse_line).peekable();
-	let width = items.peek().unwrap().len();
-	let items = items.flatten().collect_vec();
-	Grid::new(width,items)
-}
+	match grid.find_position(Item::Source) {
+		Some((_,0)) => (),
+		Some((x,y)) => return Err(ParseError::new(y+1, x+1, line(y), "the start point `S` in the top row")),
+		None => return Err(ParseError::new(1, 1, line(0), "a start point `S` in the top row")),
+	}
 
-#[derive(Deref,DerefMut,From)]
-struct Manifold(Grid);
+	let edges = [0, grid.size.width-1];
 
-impl From<&str> for Manifold {
-	fn from(value: &str) -> Self {
-		Self(parse(value))
+	if let Some(((x,y),_)) = grid.enumerate().find(|&((x,_),&item)| item == Item::Splitter && edges.contains(&x)) {
+		return Err(ParseError::new(y+1, x+1, line(y), "a splitter away from the left and right edges"))
 	}
+
+	Ok(grid)
 }
 
-impl Display for Manifold {
-	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-		let stride = self.stride();
-		for chunk in self.iter().chunks(stride).into_iter() {
-			let row = chunk.map(Item::to_string).collect_vec().join("");
-			writeln!(f,"{}",row)?;
-		}
-		std::fmt::Result::Ok(())
+#[derive(Deref,DerefMut,From,derive_more::Display)]
+struct Manifold(Grid);
+
+impl TryFrom<&str> for Manifold {
+	type Error = ParseError;
+
+	fn try_from(value: &str) -> Result<Self,Self::Error> {
+		parse(value).map(Self)
 	}
 }
 
@@ -161,6 +173,8 @@ type BeamFront = ScanLine<Beam>;
 struct ManifoldScanner<'a> {
 	manifold: &'a mut Manifold,
 	beam_front: BeamFront,
+	/// The manifold after every row scanned, when recording
+	recording: Option<Animation>,
 }
 
 #[derive(Debug,Copy,Clone)]
@@ -182,20 +196,22 @@ impl<'a> From<&'a mut Manifold> for ManifoldScanner<'a> {
 	fn from(manifold: &'a mut Manifold) -> Self {
 		let beam_front = BeamFront::new(manifold.stride());
 
-		Self { manifold, beam_front }
+		Self { manifold, beam_front, recording: None }
 	}
 }
 
 impl<'a> ManifoldScanner<'a> {
 
+	pub fn recording(self) -> Self {
+		Self { recording: Some(Animation::default()), ..self }
+	}
+
 	pub fn scan(&mut self) -> &BeamFront {
 		self.scan_downto(self.manifold.size.height-1)
 	}
 
 	pub fn scan_downto(&mut self,downto:usize) -> &BeamFront {
 
-		// println!("{}",self.beam_front);
-
 		for y in 1..=downto {
 
 			let updates = self.manifold.update(&self.beam_front);
@@ -237,7 +253,9 @@ impl<'a> ManifoldScanner<'a> {
 
 			self.beam_front.y = y;
 
-			// println!("{}",self.beam_front);
+			if let Some(animation) = &mut self.recording {
+				animation.record(self.manifold, Item::colour);
+			}
 		}
 
 		&self.beam_front
@@ -246,7 +264,7 @@ impl<'a> ManifoldScanner<'a> {
 
 impl Manifold {
 
-	fn tick<'tick>(&mut self, beam_front:&'tick BeamFront) -> Vec<BeamUpdate> {
+	fn tick(&mut self, beam_front:&BeamFront) -> Vec<BeamUpdate> {
 
 		let BeamFront { y: row, items: beams } = beam_front;
 		let mut updates:Vec<BeamUpdate> = vec![];
@@ -256,7 +274,7 @@ impl Manifold {
 			debug_assert_eq!(beams.iter().flatten().count(),0);
 
 			let (col,_) = self.find_position(Item::Source)
-				.expect("there should be a start point in the top row");
+				.expect("The start point should be checked when parsing");
 
 			updates.push(BeamUpdate::Onward(col));
 
@@ -288,7 +306,7 @@ impl Manifold {
 
 	/// Advances beams, returning the resulting BeamUpdates.
 	/// Internally calls [Manifold::tick], and updates the grid.
-	pub fn update<'tick>(&mut self, beam_front:&'tick BeamFront) -> Vec<BeamUpdate> {
+	pub fn update(&mut self, beam_front:&BeamFront) -> Vec<BeamUpdate> {
 
 		use BeamUpdate as BU;
 
@@ -319,23 +337,41 @@ impl Manifold {
 
 }
 
+/// The manifold after every row scanned
+fn record(input:&str) -> Result<Option<Animation>,ParseError> {
+
+	let manifold:&mut Manifold = &mut input.try_into()?;
+	let mut scanner = ManifoldScanner::from(manifold).recording();
+
+	scanner.scan();
+
+	Ok(scanner.recording)
+}
+
 struct Part1;
 
 impl Solution for Part1 {
 
 	const DAY: i32 = 7;
 	const PART: Part = Part::Part1;
+	type Params = ();
 
-	fn solve(input:&str) -> impl Display {
+	fn solve_with(input:&str, _:&()) -> Result<impl Display,ParseError> {
 
-		let ref mut manifold:Manifold = input.into();
+		let manifold:&mut Manifold = &mut input.try_into()?;
 		let mut scanner:ManifoldScanner = manifold.into();
 
 		let res = scanner.scan();
 
-		res.items.iter()
+		let splits = res.items.iter()
 			.flat_map(|maybe_b| maybe_b.map(|b| b.splits))
-			.sum::<usize>()
+			.sum::<usize>();
+
+		Ok(splits)
+	}
+
+	fn record_with(input:&str, _:&()) -> Result<Option<Animation>,ParseError> {
+		record(input)
 	}
 }
 
@@ -345,21 +381,30 @@ impl Solution for Part2 {
 
 	const DAY: i32 = 7;
 	const PART: Part = Part::Part2;
+	type Params = ();
 
-	fn solve(input:&str) -> impl Display {
+	fn solve_with(input:&str, _:&()) -> Result<impl Display,ParseError> {
 
-		let ref mut manifold:Manifold = input.into();
+		let manifold:&mut Manifold = &mut input.try_into()?;
 		let mut scanner:ManifoldScanner = manifold.into();
 
 		let final_beam_front = scanner.scan();
 
-		final_beam_front.items.iter()
+		let timelines = final_beam_front.items.iter()
 			.flat_map(|maybe_b| maybe_b.map(|b| b.timelines))
-			.sum::<usize>()
+			.sum::<usize>();
+
+		Ok(timelines)
+	}
+
+	fn record_with(input:&str, _:&()) -> Result<Option<Animation>,ParseError> {
+		record(input)
 	}
 
 }
 
+register! { Part1, Part2 }
+
 #[cfg(test)]
 mod test {
 
@@ -389,26 +434,54 @@ mod test {
 	#[test]
 	fn test() {
 
-		let grid = parse(EXAMPLE_INPUT);
+		let grid = parse(EXAMPLE_INPUT).unwrap();
 
 		assert_eq!(grid[(0,0)], Item::Empty);
 		assert_eq!(grid[(7,0)], Item::Source);
 		assert_eq!(grid[(7,2)], Item::Splitter);
+
+		let actual = parse(".S.\n.x.").err().unwrap();
+
+		assert_eq!((actual.line,actual.column),(2,2));
+
+		let actual = parse("...\n.^.").err().unwrap();
+		assert_eq!((actual.line,actual.column),(1,1));
+
+		let actual = parse(".S.\n...\n^..").err().unwrap();
+		assert_eq!((actual.line,actual.column),(3,1));
+
+		let actual = parse("...\n.S.").err().unwrap();
+		assert_eq!((actual.line,actual.column),(2,2));
 	}
 
 	#[test]
-	fn test_example() {
+	fn test_recording() {
+
+		let frames = record(EXAMPLE_INPUT).unwrap().unwrap();
+		let frames = frames.frames();
 
-		let actual = Part1::solve(EXAMPLE_INPUT).to_string();
-		let expected = "21";
+		let beams = |frame:&GenericGrid<Rgb>| frame.iter().filter(|&&c| c == Item::Beam.colour()).count();
 
-		assert_eq!(actual, expected);
+		assert_eq!(frames.len(), 15);
+		assert_eq!(beams(&frames[0]), 1);
+		assert!(frames.iter().map(beams).is_sorted());
+		assert!(beams(&frames[14]) > 15);
+	}
+
+	#[test]
+	fn test_snapshot() {
 
-		let actual = Part2::solve(EXAMPLE_INPUT).to_string();
-		let expected = "40";
+		let manifold:&mut Manifold = &mut EXAMPLE_INPUT.try_into().unwrap();
+		ManifoldScanner::from(&mut *manifold).scan();
+
+		assert_snapshot(manifold, "day07_example_beams");
+	}
 
-		assert_eq!(actual, expected);
+	// EXAMPLES
 
+	examples! {
+		part1: Part1 => EXAMPLE_INPUT => "21",
+		part2: Part2 => EXAMPLE_INPUT => "40",
 	}
 
 	// SOLUTIONS
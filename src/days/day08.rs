@@ -5,7 +5,7 @@
 
 use super::*;
 
-use derive_more::{Deref, Display, From, Into};
+use derive_more::{Deref, From, Into};
 
 use petgraph::{
 	prelude::*,
@@ -23,37 +23,7 @@ impl<T:Copy> IntoIterator for Pair<T> {
 	}
 }
 
-#[derive(Debug,Clone,Copy,From,Into,Hash)]
-struct Triple<T>([T;3]);
-
-
-impl<T:Copy> IntoIterator for Triple<T> {
-	type Item = T;
-	type IntoIter = <[T;3] as IntoIterator>::IntoIter;
-	fn into_iter(self) -> Self::IntoIter {
-		[self.0[0],self.0[1],self.0[2]].into_iter()
-	}
-}
-
-#[derive(Debug,Clone,Copy,Eq,PartialEq,Hash,Display,Default,PartialOrd,Ord)]
-#[display("{x},{y},{z}")]
-struct Location3 {
-	pub x: usize,
-	pub y: usize,
-	pub z: usize,
-}
-
-impl Location3 {
-	#[allow(dead_code)]
-	const ORIGIN:Self = Self { x:0 , y:0 , z:0 };
-}
-
-impl<T:Into<[usize;3]>> From<T> for Location3 {
-	fn from(value: T) -> Self {
-		let [x,y,z] = value.into();
-		Self { x,y,z }
-	}
-}
+type Location3 = Point3<usize>;
 
 type Distance = usize;
 
@@ -67,75 +37,15 @@ peg::parser! {
 		rule number() -> usize =
 			ds:$(digit()+) {? ds.parse().or(Err("Expected usize value")) }
 
-		pub rule triple<'a>() -> Triple<usize> =
-			l:(number() **<3> ",") { [l[0],l[1],l[2]].into() }
+		pub rule triple<'a>() -> Location3 =
+			l:(number() **<3> ",") { Point([l[0],l[1],l[2]]) }
 	}
 }
 
-fn parse(input:&str) -> Vec<Location3> {
-	crate::days::parse(input, parser::triple).map_into().collect_vec()
-}
-
-// Given that we only need to know the relative distances,
-// we can just use the distance squared, and save computing
-// the square root.
-fn distance_squared(l:Location3, r:Location3) -> usize {
-
-	let d:Location3 = (
-			l.x.abs_diff(r.x),
-			l.y.abs_diff(r.y),
-			l.z.abs_diff(r.z),
-		).into();
-
-	d.x * d.x + d.y * d.y + d.z * d.z
+fn parse(input:&str) -> Result<Vec<Location3>,ParseError> {
+	Ok(crate::days::parse(input, parser::triple)?.collect_vec())
 }
 
-mod group {
-
-	use std::num::NonZero;
-
-	#[derive(Debug,Clone,Copy,Eq,PartialEq)]
-	pub enum GroupId {
-		None,
-		Some(NonZero<u16>)
-	}
-
-	impl Ord for GroupId {
-		fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-			let a:u16 = (*self).into();
-			let b:u16 = (*other).into();
-			a.cmp(&b)
-		}
-	}
-
-	impl PartialOrd for GroupId {
-		fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-			Some(self.cmp(other))
-		}
-	}
-
-	impl From<u16> for GroupId {
-		fn from(value: u16) -> Self {
-			match value {
-				0 => Self::None,
-				// SAFETY: Value 0 has been excluded in the previous match arm.
-				v => unsafe { Self::Some(NonZero::new_unchecked(v)) }
-			}
-		}
-	}
-
-	impl From<GroupId> for u16 {
-		fn from(val: GroupId) -> Self {
-			match val {
-				GroupId::None => 0,
-				GroupId::Some(v) => v.get()
-			}
-		}
-	}
-}
-
-use group::GroupId;
-
 // Using a graph map we avoid duplicated nodes (same weight but different id)
 fn distance_graph<T:Sized+Clone+Iterator<Item=Location3>>(items:T) -> UnGraphMap<Location3,Distance> {
 
@@ -145,21 +55,23 @@ fn distance_graph<T:Sized+Clone+Iterator<Item=Location3>>(items:T) -> UnGraphMap
 	for [l3a,l3b] in items.array_combinations() {
 		let a = graph.add_node(l3a);
 		let b = graph.add_node(l3b);
-		graph.add_edge(a, b, distance_squared(l3a,l3b));
+		// Given that we only need to know the relative distances,
+		// we can just use the distance squared, and save computing
+		// the square root.
+		graph.add_edge(a, b, l3a.euclidean_squared(l3b));
 	}
 
 	graph
 }
 
 /// Creates a subgraph, keeping the amount of edges specified by `limit`
-fn proximity_graph<const N:usize>(distance_graph:UnGraph<Location3,Distance>) -> UnGraph<(),()> {
+fn proximity_graph(distance_graph:UnGraph<Location3,Distance>, limit:usize) -> UnGraph<(),()> {
 
-	let sorted_edge_refs_by_weight:[EdgeReference<_>;N] = distance_graph
+	let sorted_edge_refs_by_weight:Vec<EdgeReference<_>> = distance_graph
 		.edge_references()
 		.sorted_unstable_by_key(EdgeReference::weight)
-		.take(N)
-		.collect_array()
-		.unwrap();
+		.take(limit)
+		.collect();
 
 	let nodes = sorted_edge_refs_by_weight
 		.iter()
@@ -167,11 +79,10 @@ fn proximity_graph<const N:usize>(distance_graph:UnGraph<Location3,Distance>) ->
 		.unique()
 		.collect_vec();
 
-	let edges:[EdgeIndex;N] = sorted_edge_refs_by_weight
+	let edges:Vec<EdgeIndex> = sorted_edge_refs_by_weight
 		.iter()
 		.map(EdgeReference::id)
-		.collect_array()
-		.unwrap();
+		.collect();
 
 	distance_graph.filter_map(
 		|nix,_| nodes.contains(&nix).then_some(()), // default color
@@ -201,8 +112,6 @@ fn circuit_group_graph<N,E:Default>(graph:UnGraph<N,E>) -> UnGraph<GroupId,E> {
 
 			let c = color();
 
-			dbg!(c);
-
 			colored_graph[nix] = c;
 
 			while let Some(nx) = dfs.next(&colored_graph) {
@@ -214,29 +123,49 @@ fn circuit_group_graph<N,E:Default>(graph:UnGraph<N,E>) -> UnGraph<GroupId,E> {
 	colored_graph
 }
 
+/// How many of the shortest connections are made
+struct Connections {
+	limit: usize
+}
+
+impl Default for Connections {
+	fn default() -> Self {
+		Self { limit: 1000 }
+	}
+}
+
+impl Params for Connections {
+	fn set(&mut self, key:&str, value:&str) -> Result<(),String> {
+		match key {
+			"limit" => self.limit = param(key,value)?,
+			_ => return Err(format!("Unknown parameter `{key}`, expected `limit`")),
+		}
+		Ok(())
+	}
+}
+
 struct Part1;
 
 impl Solution for Part1 {
 
 	const DAY: i32 = 8;
 	const PART: Part = Part::Part1;
+	type Params = Connections;
 
-	fn solve(input:&str) -> impl Display {
+	fn solve_with(input:&str, connections:&Connections) -> Result<impl Display,ParseError> {
 
-		let box_locations = parse(input).into_iter();
+		let box_locations = parse(input)?.into_iter();
 		let distance_graph:UnGraph<Location3,Distance> = distance_graph(box_locations).into_graph();
-		Self::solve_for::<1000>(distance_graph)
+		Ok(Self::solve_for(distance_graph, connections.limit))
 	}
 }
 
 impl Part1 {
 
-	/// Solve considering only the top `LIMIT` shortest connections
-	fn solve_for<const LIMIT:usize>(distance_graph:UnGraph<Location3,Distance>)-> impl Display {
-
-		let proximity_graph = proximity_graph::<LIMIT>(distance_graph);
+	/// Solve considering only the top `limit` shortest connections
+	fn solve_for(distance_graph:UnGraph<Location3,Distance>, limit:usize)-> impl Display {
 
-		dbg!(proximity_graph.raw_edges());
+		let proximity_graph = proximity_graph(distance_graph, limit);
 
 		let circuit_group_graph = circuit_group_graph(proximity_graph);
 
@@ -257,12 +186,19 @@ impl Solution for Part2 {
 
 	const DAY: i32 = 8;
 	const PART: Part = Part::Part2;
+	type Params = ();
 
-	fn solve(input:&str) -> impl Display {
+	fn solve_with(input:&str, _:&()) -> Result<impl Display,ParseError> {
 
-		let box_locations = parse(input).into_iter();
+		let box_locations = parse(input)?.into_iter();
 		let distance_graph:UnGraph<Location3,Distance> = distance_graph(box_locations).into_graph();
 		let total_nodes = distance_graph.node_count();
+
+		// A single box never forms a circuit
+		if total_nodes < 2 {
+			return Err(ParseError::new(input.lines().count() + 1, 1, "", "at least two distinct junction boxes"))
+		}
+
 		let mut node_seen_graph = distance_graph.map(|_,_|false, |_,_|());
 
 		let mut sorted_edge_refs_by_weight = distance_graph
@@ -285,13 +221,15 @@ impl Solution for Part2 {
 
 			if connected_nodes >= total_nodes {
 
-				break distance_graph[edge.source()].x * distance_graph[edge.target()].x
+				break Ok(distance_graph[edge.source()].x() * distance_graph[edge.target()].x())
 			}
 		}
 
 	}
 }
 
+register! { Part1, Part2 }
+
 #[cfg(test)]
 mod test {
 
@@ -325,7 +263,7 @@ mod test {
 	#[test]
 	fn test_parse() {
 
-		let mut locations = parse(EXAMPLE_INPUT).into_iter();
+		let mut locations = parse(EXAMPLE_INPUT).unwrap().into_iter();
 
 		let actual:Location3 = locations.next().unwrap();
 		let expected:Location3 = [162,817,812].into();
@@ -339,22 +277,20 @@ mod test {
 	}
 
 	#[test]
-	fn test_part1() {
+	fn test_single_box() {
 
-		let box_locations = parse(EXAMPLE_INPUT).into_iter();
-		let distance_graph:UnGraph<Location3,Distance> = distance_graph(box_locations).into_graph();
+		let actual = Part2::solve("1,2,3\n").err().unwrap();
+		assert_eq!((actual.line,actual.column),(2,1));
 
-		let expected = "40";
-		let actual = Part1::solve_for::<10>(distance_graph).to_string();
-		assert_eq!(actual,expected);
+		assert!(Part2::solve("1,2,3\n1,2,3\n").is_err());
+		assert_eq!(Part2::solve("1,2,3\n4,5,6\n").unwrap().to_string(), "4");
 	}
 
-	#[test]
-	fn test_part2() {
+	// EXAMPLES
 
-		let expected = "25272";
-		let actual = Part2::solve(EXAMPLE_INPUT).to_string();
-		assert_eq!(actual,expected)
+	examples! {
+		part1: Part1(Connections { limit: 10 }) => EXAMPLE_INPUT => "40",
+		part2: Part2 => EXAMPLE_INPUT => "25272",
 	}
 
 	// SOLUTIONS
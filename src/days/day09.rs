@@ -0,0 +1,248 @@
+// https://adventofcode.com/2025/day/9
+
+
+use super::*;
+
+peg::parser! {
+
+	grammar parser() for str {
+
+		rule digit() -> char =
+			[c if c.is_ascii_digit()]
+
+		rule number() -> usize =
+			ds:$(digit()+) {? ds.parse().or(Err("Expected usize value")) }
+
+		pub rule tile() -> Location =
+			x:number() "," y:number() { (x,y).into() }
+	}
+}
+
+fn parse(input:&str) -> Result<Vec<Location>,ParseError> {
+	Ok(crate::days::parse(input, parser::tile)?.collect_vec())
+}
+
+/// The tiles of the rectangle with opposite corners `a` and `b`, both included
+fn area(a:Location, b:Location) -> usize {
+	(a.x.abs_diff(b.x) + 1) * (a.y.abs_diff(b.y) + 1)
+}
+
+struct Part1;
+
+impl Solution for Part1 {
+
+	const DAY: i32 = 9;
+	const PART: Part = Part::Part1;
+	type Params = ();
+
+	fn solve_with(input:&str, _:&()) -> Result<impl Display,ParseError> {
+
+		let red = parse(input)?;
+
+		let largest = red.into_iter()
+			.tuple_combinations()
+			.map(|(a,b)| area(a,b))
+			.max()
+			.unwrap_or(0);
+
+		Ok(largest)
+	}
+}
+
+/// The distinct values of a coordinate, sorted.
+///
+/// The `i`th value is compressed to the odd cell `2i+1`, and the even cells
+/// between them stand for the tiles in between, if any. The first and last cells are padding.
+struct Axis(Vec<usize>);
+
+impl Axis {
+
+	fn new(values:impl Iterator<Item=usize>) -> Self {
+		Self(values.sorted_unstable().dedup().collect())
+	}
+
+	fn len(&self) -> usize {
+		2 * self.0.len() + 1
+	}
+
+	/// The cell of a value in the axis
+	fn cell(&self, value:usize) -> usize {
+		2 * self.0.binary_search(&value).expect("The value should be in the axis") + 1
+	}
+
+	/// How many tiles a cell stands for
+	fn tiles(&self, cell:usize) -> usize {
+		match cell {
+			c if c % 2 == 1 => 1,
+			0 => 0,
+			c if c == self.len() - 1 => 0,
+			c => self.0[c/2] - self.0[c/2 - 1] - 1,
+		}
+	}
+}
+
+/// The floor, compressed so that only the rows and columns of red tiles,
+/// and the gaps between them, remain
+struct Floor {
+	xs: Axis,
+	ys: Axis,
+	/// How many tiles outside the loop are above and left of each cell, excluded.
+	/// It has an extra row and column, to sum the whole floor.
+	outside: Grid<usize>,
+}
+
+impl Floor {
+
+	/// Joins the red tiles in order, and back to the first, with green tiles
+	fn new(red:&[Location]) -> Self {
+
+		let xs = Axis::new(red.iter().map(|t| t.x));
+		let ys = Axis::new(red.iter().map(|t| t.y));
+
+		let compress = |t:&Location| Location::from((xs.cell(t.x), ys.cell(t.y)));
+
+		let mut boundary = Grid::filled((xs.len(), ys.len()).into(), false)
+			.expect("The axes should have their padding");
+
+		for (a,b) in red.iter().map(compress).circular_tuple_windows() {
+			for x in a.x.min(b.x)..=a.x.max(b.x) {
+				for y in a.y.min(b.y)..=a.y.max(b.y) {
+					boundary[(x,y)] = true;
+				}
+			}
+		}
+
+		// Thanks to the padding, the outside is connected around the loop
+		let outer = boundary.bfs(Location::from((0,0)), |&b| !b);
+
+		let mut outside = Grid::filled((xs.len() + 1, ys.len() + 1).into(), 0)
+			.expect("The axes should have their padding");
+
+		for y in 0..ys.len() {
+			for x in 0..xs.len() {
+				let tiles = match outer.distance((x,y).into()) {
+					Some(_) => xs.tiles(x) * ys.tiles(y),
+					None => 0,
+				};
+				outside[(x+1,y+1)] = tiles + outside[(x,y+1)] + outside[(x+1,y)] - outside[(x,y)];
+			}
+		}
+
+		Self { xs, ys, outside }
+	}
+
+	/// Whether the rectangle with opposite red corners `a` and `b`
+	/// has only red or green tiles
+	fn is_covered(&self, a:Location, b:Location) -> bool {
+
+		let (x0,x1) = (self.xs.cell(a.x.min(b.x)), self.xs.cell(a.x.max(b.x)) + 1);
+		let (y0,y1) = (self.ys.cell(a.y.min(b.y)), self.ys.cell(a.y.max(b.y)) + 1);
+
+		let o = &self.outside;
+
+		o[(x1,y1)] + o[(x0,y0)] == o[(x0,y1)] + o[(x1,y0)]
+	}
+}
+
+struct Part2;
+
+impl Solution for Part2 {
+
+	const DAY: i32 = 9;
+	const PART: Part = Part::Part2;
+	type Params = ();
+
+	fn solve_with(input:&str, _:&()) -> Result<impl Display,ParseError> {
+
+		let red = parse(input)?;
+		let floor = Floor::new(&red);
+
+		let largest = red.into_iter()
+			.tuple_combinations()
+			.map(|(a,b)| (area(a,b),a,b))
+			.sorted_unstable_by(|l,r| r.0.cmp(&l.0))
+			.find(|&(_,a,b)| floor.is_covered(a,b))
+			.map_or(0, |(area,..)| area);
+
+		Ok(largest)
+	}
+}
+
+register! { Part1, Part2 }
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	const EXAMPLE_INPUT:&str = indoc! {"
+		7,1
+		11,1
+		11,7
+		9,7
+		9,5
+		2,5
+		2,3
+		7,3
+	"};
+
+	#[test]
+	fn test_parse() {
+
+		let red = parse(EXAMPLE_INPUT).unwrap();
+
+		assert_eq!(red.len(), 8);
+		assert_eq!(red[0], (7,1).into());
+		assert_eq!(red[7], (7,3).into());
+
+		assert!(parse("7;1").is_err());
+	}
+
+	#[test]
+	fn test_area() {
+
+		// "... a rectangle with an area as large as 50 between 2,5 and 11,1"
+		assert_eq!(area((2,5).into(), (11,1).into()), 50);
+		assert_eq!(area((7,3).into(), (2,3).into()), 6);
+	}
+
+	#[test]
+	fn test_axis() {
+
+		let axis = Axis::new([7,11,2,7,9].into_iter());
+
+		assert_eq!(axis.len(), 9);
+		assert_eq!(axis.cell(2), 1);
+		assert_eq!(axis.cell(11), 7);
+
+		let tiles = (0..axis.len()).map(|c| axis.tiles(c)).collect_vec();
+		assert_eq!(tiles, vec![0,1,4,1,1,1,1,1,0]);
+	}
+
+	#[test]
+	fn test_covered() {
+
+		let red = parse(EXAMPLE_INPUT).unwrap();
+		let floor = Floor::new(&red);
+
+		// "... a rectangle with area 24 between 9,5 and 2,3"
+		assert!(floor.is_covered((9,5).into(), (2,3).into()));
+		assert!(floor.is_covered((7,3).into(), (11,1).into()));
+
+		// Reaching the outside, below 9,5 or left of 7,1
+		assert!(!floor.is_covered((2,5).into(), (11,7).into()));
+		assert!(!floor.is_covered((2,3).into(), (7,1).into()));
+	}
+
+	// EXAMPLES
+
+	examples! {
+		part1: Part1 => EXAMPLE_INPUT => "50",
+		part2: Part2 => EXAMPLE_INPUT => "24",
+	}
+
+	// SOLUTIONS
+
+	submit! { Part1 }
+	submit! { Part2 }
+}
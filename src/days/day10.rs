@@ -2,21 +2,38 @@
 
 
 use derive_more::{Deref, From, Into};
+use num::{PrimInt, Rational64, Zero, One};
 
 use super::*;
 
+/// The integers that can hold the lights of a machine, one bit each
+trait Bits: PrimInt + Default + std::fmt::Debug {
+	const WIDTH: usize;
+}
+
+macro_rules! bits {
+	($($t:ty),+) => {
+		$( impl Bits for $t { const WIDTH: usize = <$t>::BITS as usize; } )+
+	};
+}
+
+bits!(u16, u32, u64, u128);
+
+/// The width the machines are read with.
+/// The size 16 has been chosen by assessing the inputs.
+type Width = u16;
+
 // Represents the machine state, as bits
-// The size 16 has been chosen by assessing the inputs.
-#[derive(Clone,Copy,Debug,Default,Into,PartialEq,Eq)]
-struct MachineState(u16);
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq)]
+struct MachineState<B=Width>(B);
 
-impl AsRef<u16> for MachineState {
-	fn as_ref(&self) -> &u16 {
+impl<B> AsRef<B> for MachineState<B> {
+	fn as_ref(&self) -> &B {
 		&self.0
 	}
 }
 
-impl<T:Clone+IntoIterator<Item=Option<()>>> From<T> for MachineState {
+impl<B:Bits,T:Clone+IntoIterator<Item=Option<()>>> From<T> for MachineState<B> {
 	fn from(options: T) -> Self {
 		options
 			.into_iter()
@@ -26,39 +43,59 @@ impl<T:Clone+IntoIterator<Item=Option<()>>> From<T> for MachineState {
 	}
 }
 
-impl MachineState {
+impl<B:Bits> MachineState<B> {
 
-	pub fn toggle<T:Into<u16>>(self,mask:T) -> Self {
-		let inner:u16 = self.into();
-		let xor_mask:u16 = mask.into();
-		Self(inner ^ xor_mask)
+	pub fn toggle<T:Into<B>>(self,mask:T) -> Self {
+		Self(self.0 ^ mask.into())
 	}
 
+	/// Panics past the width of the bitset
 	pub fn toggle_bit(self,n:usize) -> Self {
-		let mask = 2u16.pow(n as u32);
-		self.toggle(mask)
+		assert!(n < B::WIDTH, "Bit {n} is outside a bitset of width {}", B::WIDTH);
+		self.toggle(B::one() << n)
+	}
+
+	/// Renders the first lights as in the manual, e.g. `[.##.]`
+	pub fn diagram(self,lights:usize) -> String {
+		let light = |i| if (self.0 >> i) & B::one() == B::one() { '#' } else { '.' };
+		format!("[{}]", (0..lights).map(light).collect::<String>())
 	}
 }
 
 // Represents the flips the button performs, as a bitmask
 #[derive(Debug,Clone,Copy,Default,PartialEq,Eq)]
-struct Button(u16);
+struct Button<B=Width>(B);
 
-impl AsRef<u16> for Button {
-	fn as_ref(&self) -> &u16 {
+impl<B> AsRef<B> for Button<B> {
+	fn as_ref(&self) -> &B {
 		&self.0
 	}
 }
 
-impl<T:IntoIterator<Item=u8>> From<T> for Button {
+/// Panics past the width of the bitset, that the parser checks for
+impl<B:Bits,T:IntoIterator<Item=u8>> From<T> for Button<B> {
 	fn from(value: T) -> Self {
 		let inner = value
 			.into_iter()
-			.fold(0u16, |v,b| { v | 2u16.pow(b as u32) });
+			.fold(B::zero(), |v,b| { v | MachineState(B::zero()).toggle_bit(b as usize).0 });
 		Self(inner)
 	}
 }
 
+impl<B:Bits> Button<B> {
+	/// The indices of the lights, or counters, the button acts upon
+	fn wires(self) -> impl Iterator<Item=usize> {
+		(0..B::WIDTH).filter(move |&i| (self.0 >> i) & B::one() == B::one())
+	}
+}
+
+/// As in the manual, e.g. `(1,3)`
+impl<B:Bits> Display for Button<B> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "({})", self.wires().join(","))
+	}
+}
+
 impl<V:Copy,T:AsRef<V>> IntoInner<V> for T {
 	fn into_inner(self) -> V {
 		*self.as_ref()
@@ -66,36 +103,107 @@ impl<V:Copy,T:AsRef<V>> IntoInner<V> for T {
 }
 
 #[derive(Debug,Default)]
-struct MachineDescription {
-	target: MachineState,
-	buttons: Vec<Button>,
+struct MachineDescription<B=Width> {
+	/// How many lights there are
+	lights: usize,
+	target: MachineState<B>,
+	buttons: Vec<Button<B>>,
+	joltage: Vec<usize>,
 }
 
-impl MachineDescription {
-	fn state_iter<'a>(&'a self,is:impl IntoIterator<Item=usize>) -> MachineStateSequence<'a,impl Iterator<Item=usize>> {
+impl<B:Bits> MachineDescription<B> {
+	fn state_iter<'a>(&'a self,is:impl IntoIterator<Item=usize>) -> MachineStateSequence<'a,B,impl Iterator<Item=usize>> {
 		MachineStateSequence { buttons: &self.buttons, state: MachineState::default(), iter: is.into_iter() }
 	}
 }
 
-struct MachineStateSequence<'a,I:Iterator<Item=usize>> {
-	state: MachineState,
-	buttons: &'a Vec<Button>,
+struct MachineStateSequence<'a,B,I:Iterator<Item=usize>> {
+	state: MachineState<B>,
+	buttons: &'a Vec<Button<B>>,
 	iter: I
 }
 
-impl<'a,I:Iterator<Item=usize>> Iterator for MachineStateSequence<'a,I> {
+impl<'a,B:Bits,I:Iterator<Item=usize>> Iterator for MachineStateSequence<'a,B,I> {
 
-	type Item = MachineState;
+	type Item = MachineState<B>;
 
 	fn next(&mut self) -> Option<Self::Item> {
 		let i = self.iter.next()?;
 		let btn = self.buttons.get(i).expect("Requested button should exist.");
-		let mask:u16 = btn.into_inner();
+		let mask:B = btn.into_inner();
 		self.state = self.state.toggle(mask);
 		Some(self.state)
 	}
 }
 
+/// A machine being operated, one press at a time
+struct Machine<'a,B=Width> {
+	description: &'a MachineDescription<B>,
+	state: MachineState<B>,
+	history: Vec<usize>,
+}
+
+impl<'a,B:Bits> Machine<'a,B> {
+
+	/// The machine with every light off
+	fn new(description:&'a MachineDescription<B>) -> Self {
+		Self { description, state: MachineState::default(), history: vec![] }
+	}
+
+	/// Presses the `i`th button, giving the new state,
+	/// or none if there is no such button
+	fn press(&mut self, i:usize) -> Option<MachineState<B>> {
+		let button = self.description.buttons.get(i)?;
+		self.state = self.state.toggle(button.into_inner());
+		self.history.push(i);
+		Some(self.state)
+	}
+
+	/// Turns every light off, and forgets the presses
+	#[allow(dead_code)]
+	fn reset(&mut self) {
+		self.state = MachineState::default();
+		self.history.clear();
+	}
+
+	/// The buttons pressed so far, in order
+	fn history(&self) -> &[usize] {
+		&self.history
+	}
+
+	/// The current lights
+	#[allow(dead_code)]
+	fn state(&self) -> MachineState<B> {
+		self.state
+	}
+
+	/// Whether the lights match the target
+	#[allow(dead_code)]
+	fn is_lit(&self) -> bool {
+		self.state == self.description.target
+	}
+
+	/// The lights before any press, then after each one, along with the button pressed
+	fn trace(&self) -> String {
+
+		let lights = self.description.lights;
+		let states = self.description.state_iter(self.history.iter().copied());
+
+		std::iter::once(MachineState::<B>::default().diagram(lights))
+			.chain(self.history.iter().zip(states).map(|(&i,state)| {
+				format!("{} {}", state.diagram(lights), self.description.buttons[i])
+			}))
+			.join("\n")
+	}
+}
+
+/// The current lights, e.g. `[.##.]`
+impl<B:Bits> Display for Machine<'_,B> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(&self.state.diagram(self.description.lights))
+	}
+}
+
 peg::parser! {
 
 	grammar parser() for str {
@@ -108,22 +216,91 @@ peg::parser! {
 
 		rule light() -> Option<()> = ['.'] { None } / ['#'] { Some(()) }
 
-		rule state() -> MachineState =
-			"[" s:(light()+) "]" { s.into() }
+		rule lights() -> Vec<Option<()>> =
+			"[" s:(light()+) "]" { s }
 
-		rule button() -> Button =
-			"(" bits:(number() ++ ",") ")" {
-				bits.into_iter().map(|n| n as u8).into()
-			}
+		rule wire() -> (usize,usize) =
+			p:position!() n:number() { (p,n) }
+
+		rule button() -> Vec<(usize,usize)> =
+			"(" ws:(wire() ++ ",") ")" { ws }
 
-		pub rule machine() -> MachineDescription =
-			target:state() " " buttons:(button() ++ " ") [_]* {
-				MachineDescription { target, buttons }
+		rule joltage() -> (usize,Vec<usize>) =
+			p:position!() "{" js:(number() ++ ",") "}" { (p,js) }
+
+		/// A machine with the lights in a bitset of type `B`,
+		/// or the offset and description of what does not fit them
+		pub rule machine<B:Bits>() -> Result<MachineDescription<B>,(usize,String)> =
+			lights:lights() " " p:position!() buttons:(button() ++ " ") " " joltage:joltage() {
+				MachineDescription::new(lights, (p,buttons), joltage)
 			}
 	}
 }
 
-fn find_shortest_activation_seq(m:MachineDescription)->impl Iterator<Item=usize> {
+impl<B:Bits> MachineDescription<B> {
+
+	/// Checks that the lights fit the bitset, that the buttons are wired to them,
+	/// and that there is a joltage counter for each one.
+	/// The offsets locate the errors in the line.
+	fn new(
+		lights:Vec<Option<()>>,
+		(at,buttons):(usize,Vec<Vec<(usize,usize)>>),
+		(joltage_at,joltage):(usize,Vec<usize>)
+	) -> Result<Self,(usize,String)> {
+
+		let count = lights.len();
+
+		if count > B::WIDTH {
+			return Err((0, format!("at most {} lights, the bitset width, not {count}", B::WIDTH)))
+		}
+
+		if buttons.len() > Presses::BITS as usize {
+			return Err((at, format!("at most {} buttons, not {}", Presses::BITS, buttons.len())))
+		}
+
+		if let Some(&(offset,n)) = buttons.iter().flatten().find(|&&(_,n)| n >= count) {
+			return Err((offset, format!("a light index below {count}, within a bitset of width {}, not {n}", B::WIDTH)))
+		}
+
+		if joltage.len() != count {
+			return Err((joltage_at, format!("{count} joltage counters, one per light, not {}", joltage.len())))
+		}
+
+		Ok(Self {
+			lights: count,
+			target: lights.into(),
+			buttons: buttons.into_iter().map(|b| b.into_iter().map(|(_,n)| n as u8).into()).collect(),
+			joltage,
+		})
+	}
+}
+
+/// Reads the machine at line index `n`
+fn machine_of<B:Bits>(n:usize, line:&str) -> Result<MachineDescription<B>,ParseError> {
+	parse_line(n, line, parser::machine::<B>)?
+		.map_err(|(offset,expected)| ParseError::new(n+1, offset+1, line, expected))
+}
+
+/// Solves every machine, reporting the first without a solution at its line
+fn solve_each<T>(input:&str, expected:&str, solve:impl Fn(&MachineDescription) -> Option<T>) -> Result<Vec<T>,ParseError> {
+	input.lines()
+		.enumerate()
+		.map(|(n,line)| {
+			let m = machine_of(n, line)?;
+			solve(&m).ok_or_else(|| ParseError::new(n+1, 1, line, expected))
+		})
+		.collect()
+}
+
+/// Which buttons are pressed once each, as a bitmask over their indices
+type Presses = u32;
+
+/// The indices of the pressed buttons
+fn pressed(presses:Presses) -> impl Iterator<Item=usize> {
+	(0..Presses::BITS as usize).filter(move |i| presses >> i & 1 == 1)
+}
+
+fn find_shortest_activation_seq<B:Bits>(m:&MachineDescription<B>)->Option<impl Iterator<Item=usize>> {
 
 	// Button presses are just XOR operations on the machine state.
 	// Given that XOR:
@@ -139,33 +316,337 @@ fn find_shortest_activation_seq(m:MachineDescription)->impl Iterator<Item=usize>
 
 	(0..m.buttons.len())
 		.powerset()
-		// first is a []
-		.skip(1)
+		// first is a [], the answer for machines whose lights all stay off
 		// ow, skip seqs that not achieve desired state
-		.skip_while(|is| {
+		.find(|is| {
 			// println!("{:?}",&is);
-			m.state_iter(is.iter().copied()).last().unwrap() != m.target
-		}).next().unwrap()
-			.into_iter()
+			m.state_iter(is.iter().copied()).last().unwrap_or_default() == m.target
+		})
+			.map(Vec::into_iter)
+}
+
+/// Reduces a light pattern, made by the given presses, with the vectors of a basis
+fn reduce<B:Bits>(basis:&[(B,Presses)], (mut lights,mut presses):(B,Presses)) -> (B,Presses) {
+	for &(v,p) in basis {
+		let leading = B::one() << (B::WIDTH - 1 - v.leading_zeros() as usize);
+		if !(lights & leading).is_zero() {
+			lights = lights ^ v;
+			presses ^= p;
+		}
+	}
+	(lights,presses)
+}
+
+/// The fewest presses that light the machine, by linear algebra over GF(2).
+///
+/// As pressing twice does nothing, presses are a vector of bits, and so are the lights.
+/// The buttons make a matrix, that is row-reduced into a basis of the patterns they can light,
+/// along with the combinations of presses that light nothing, its null space.
+/// Every solution is one of them added to a particular solution,
+/// so only the null space is enumerated.
+fn find_fewest_presses<B:Bits>(m:&MachineDescription<B>) -> Option<Presses> {
+
+	// Each vector with a distinct leading bit, and the presses that light it
+	let mut basis:Vec<(B,Presses)> = vec![];
+	let mut null = vec![];
+
+	for (b,button) in m.buttons.iter().enumerate() {
+		match reduce(&basis, (button.into_inner(), 1 << b)) {
+			(lights,presses) if lights.is_zero() => null.push(presses),
+			reduced => basis.push(reduced),
+		}
+	}
+
+	let (unlit,particular) = reduce(&basis, (m.target.into_inner(), 0));
+
+	if !unlit.is_zero() {
+		return None
+	}
+
+	null.into_iter()
+		.powerset()
+		.map(|ns| ns.into_iter().fold(particular, |p,n| p ^ n))
+		.min_by_key(|p| p.count_ones())
+}
+
+/// How to find the fewest presses that light a machine
+#[derive(Debug,Clone,Copy,Default,PartialEq)]
+enum Method {
+	/// Row reduction over GF(2), see [find_fewest_presses]
+	#[default]
+	Elimination,
+	/// Trying every set of buttons, see [find_shortest_activation_seq]
+	Powerset,
+}
+
+impl Method {
+	/// The buttons to press once each, for the fewest presses that light the machine,
+	/// if it can be lit at all
+	fn presses<B:Bits>(self, m:&MachineDescription<B>) -> Option<Vec<usize>> {
+		match self {
+			Method::Elimination => find_fewest_presses(m).map(|presses| pressed(presses).collect()),
+			Method::Powerset => find_shortest_activation_seq(m).map(Iterator::collect),
+		}
+	}
+}
+
+impl std::str::FromStr for Method {
+	type Err = ();
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"elimination" => Ok(Method::Elimination),
+			"powerset" => Ok(Method::Powerset),
+			_ => Err(()),
+		}
+	}
+}
+
+#[derive(Debug,Default)]
+struct Lights {
+	method: Method,
+}
+
+impl Params for Lights {
+	fn set(&mut self, key:&str, value:&str) -> Result<(),String> {
+		match key {
+			"method" => self.method = param(key,value)?,
+			_ => return Err(format!("Unknown parameter `{key}`, expected `method`")),
+		}
+		Ok(())
+	}
 }
 
+/// What a machine that cannot be lit is expected to be
+const LIT:&str = "a machine whose lights can be lit";
+
 struct Part1;
 
 impl Solution for Part1 {
 
 	const DAY: i32 = 10;
 	const PART: Part = Part::Part1;
+	type Params = Lights;
 
-	fn solve(input:&str) -> impl Display {
+	fn solve_with(input:&str, params:&Lights) -> Result<impl Display,ParseError> {
 
-		let machines = parse(input, parser::machine);
+		let presses = solve_each(input, LIT, |m| params.method.presses(m))?;
 
-		machines
-			.map(|m| find_shortest_activation_seq(m).count() )
-			.sum::<usize>()
+		Ok(presses.iter().map(Vec::len).sum::<usize>())
+	}
+
+	/// The buttons chosen for each machine, and the lights after each press
+	fn explain_with(input:&str, params:&Lights) -> Result<Option<String>,ParseError> {
+
+		let traces = solve_each(input, LIT, |m| {
+			let mut machine = Machine::new(m);
+			for i in params.method.presses(m)? {
+				machine.press(i);
+			}
+			let buttons = machine.history().iter().map(|&i| m.buttons[i]).join(" ");
+			Some(format!("{} presses, {buttons}\n{}", machine.history().len(), machine.trace()))
+		})?;
+
+		let explanation = traces.into_iter()
+			.enumerate()
+			.map(|(n,trace)| format!("Machine {}: {trace}", n+1))
+			.join("\n\n");
+
+		Ok(Some(explanation))
 	}
 }
 
+/// The equations `Σ presses[b] = joltage[c]`, over the buttons `b` wired to each counter `c`,
+/// once solved for as many presses as possible
+#[derive(Debug)]
+struct JoltageSystem {
+	/// The solved presses, in terms of the free ones
+	equations: Vec<Equation>,
+	/// The presses not solved for, to be searched
+	free: Vec<usize>,
+	/// The most presses of each button, before one of its counters overflows
+	bounds: Vec<usize>,
+}
+
+/// A solved number of presses `(constant - Σ coefficients[f]·free[f]) / divisor`,
+/// scaled to whole numbers
+#[derive(Debug,PartialEq)]
+struct Equation {
+	coefficients: Vec<i64>,
+	constant: i64,
+	divisor: i64,
+}
+
+impl Equation {
+
+	/// Scales a row of the reduced matrix, that solves for one button,
+	/// keeping the coefficients of the free buttons
+	fn scaled(row:&[Rational64], free:&[usize]) -> Self {
+
+		let divisor = row.iter().fold(1, |lcm,v| num::integer::lcm(lcm, *v.denom()));
+		let scale = |v:Rational64| (v * divisor).to_integer();
+
+		Self {
+			coefficients: free.iter().map(|&f| scale(row[f])).collect(),
+			constant: scale(row[row.len()-1]),
+			divisor,
+		}
+	}
+}
+
+impl JoltageSystem {
+
+	/// Gaussian elimination of the machine equations, over the rationals,
+	/// or none if the joltage cannot be reached, even with fractional presses
+	fn new<B:Bits>(m:&MachineDescription<B>) -> Option<Self> {
+
+		let n = m.buttons.len();
+
+		let mut rows = m.joltage.iter()
+			.map(|&j| {
+				let mut row = vec![Rational64::zero();n+1];
+				row[n] = Rational64::from_integer(j as i64);
+				row
+			})
+			.collect_vec();
+
+		for (b,button) in m.buttons.iter().enumerate() {
+			for c in button.wires() {
+				// A button wired to a missing counter can never be pressed
+				rows.get_mut(c)?[b] = Rational64::one();
+			}
+		}
+
+		let bounds = m.buttons.iter()
+			.map(|button| button.wires().map(|c| m.joltage[c]).min().unwrap_or(0))
+			.collect_vec();
+
+		let mut rank = 0;
+		let mut free = vec![];
+
+		for col in 0..n {
+
+			let r = rank;
+
+			let Some(p) = (r..rows.len()).find(|&i| !rows[i][col].is_zero()) else {
+				free.push(col);
+				continue
+			};
+
+			rows.swap(r,p);
+
+			let pivot = rows[r][col];
+			rows[r].iter_mut().for_each(|v| *v /= pivot);
+
+			let pivot_row = rows[r].clone();
+
+			for row in rows.iter_mut().enumerate().filter(|(i,_)| *i != r).map(|(_,row)| row) {
+				let factor = row[col];
+				if factor.is_zero() { continue }
+				row.iter_mut().zip(&pivot_row).for_each(|(v,p)| *v -= factor * p);
+			}
+
+			rank += 1;
+		}
+
+		// Left over equations read `0 = constant`
+		if rows[rank..].iter().any(|row| !row[n].is_zero()) {
+			return None
+		}
+
+		let equations = rows[..rank].iter()
+			.map(|row| Equation::scaled(row, &free))
+			.collect();
+
+		Some(Self { equations, free, bounds })
+	}
+
+	/// The fewest presses in total, over every whole, non-negative solution
+	fn fewest_presses(&self) -> Option<usize> {
+
+		let bounds = self.free.iter().map(|&f| self.bounds[f] as i64).collect_vec();
+
+		// How much the free presses from the `i`th one on may lower, or raise,
+		// the numerator of each equation, at most
+		let mut reach = vec![vec![(0,0);self.equations.len()];bounds.len()+1];
+
+		for i in (0..bounds.len()).rev() {
+			for (r,eq) in self.equations.iter().enumerate() {
+				let (low,high) = reach[i+1][r];
+				let change = eq.coefficients[i] * bounds[i];
+				reach[i][r] = (low + change.min(0), high + change.max(0));
+			}
+		}
+
+		let mut residuals = self.equations.iter().map(|eq| eq.constant).collect_vec();
+		let mut best = None;
+
+		self.search(&bounds, &reach, &mut residuals, 0, 0, &mut best);
+
+		best.map(|b| b as usize)
+	}
+
+	/// Tries every value of the free presses from the `i`th one on,
+	/// keeping the best total.
+	/// The residuals are the numerators of the equations, with the earlier free presses applied.
+	fn search(&self, bounds:&[i64], reach:&[Vec<(i64,i64)>], residuals:&mut [i64], i:usize, sum:i64, best:&mut Option<i64>) {
+
+		let mut least = sum;
+
+		for ((eq,&residual),&(low,high)) in self.equations.iter().zip(residuals.iter()).zip(&reach[i]) {
+
+			// No later presses can make this button pressed a non-negative number of times
+			if residual - low < 0 {
+				return
+			}
+
+			least += (residual - high).max(0) / eq.divisor;
+		}
+
+		if best.is_some_and(|b| least >= b) {
+			return
+		}
+
+		if i == bounds.len() {
+
+			if self.equations.iter().zip(residuals.iter()).all(|(eq,r)| r % eq.divisor == 0) {
+				*best = Some(least);
+			}
+
+			return
+		}
+
+		for v in 0..=bounds[i] {
+
+			self.equations.iter().zip(residuals.iter_mut()).for_each(|(eq,r)| *r -= eq.coefficients[i] * v);
+
+			self.search(bounds, reach, residuals, i+1, sum+v, best);
+
+			self.equations.iter().zip(residuals.iter_mut()).for_each(|(eq,r)| *r += eq.coefficients[i] * v);
+		}
+	}
+}
+
+struct Part2;
+
+impl Solution for Part2 {
+
+	const DAY: i32 = 10;
+	const PART: Part = Part::Part2;
+	type Params = ();
+
+	fn solve_with(input:&str, _:&()) -> Result<impl Display,ParseError> {
+
+		let presses = solve_each(input, "a machine whose joltage can be reached", |m| {
+			JoltageSystem::new(m)?.fewest_presses()
+		})?;
+
+		Ok(presses.into_iter().sum::<usize>())
+	}
+}
+
+register! { Part1, Part2 }
+
 #[cfg(test)]
 mod test {
 	use std::vec;
@@ -180,6 +661,10 @@ use super::*;
 		"#
 	};
 
+	fn read(line:&str) -> Result<MachineDescription,ParseError> {
+		machine_of(0, line)
+	}
+
 	#[test]
 	fn test_state() {
 
@@ -195,7 +680,7 @@ use super::*;
 
 		assert_eq!(bit_by_bit,MachineState(0b101));
 
-		let reset = bit_by_bit.toggle(0b101 as u16);
+		let reset = bit_by_bit.toggle(0b101u16);
 
 		assert_eq!(reset,MachineState::default());
 	}
@@ -215,7 +700,7 @@ use super::*;
 	fn test_parse() {
 
 		let input = "[.##.] (3) (1,3) (2) {3,5,4,7}";
-		let machine = parser::machine(input).unwrap();
+		let machine = read(input).unwrap();
 
 		let expected_target = MachineState(0b0110);
 		let actual_target = machine.target;
@@ -231,27 +716,59 @@ use super::*;
 		let actual_buttons = machine.buttons;
 
 		assert_equal(actual_buttons, expected_buttons);
+
+		assert_eq!(machine.joltage, vec![3,5,4,7]);
+	}
+
+	#[test]
+	fn test_joltage() {
+
+		let input = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}";
+		let machine = read(input).unwrap();
+
+		let system = JoltageSystem::new(&machine).unwrap();
+
+		assert_eq!(system.equations.len() + system.free.len(), 6);
+		assert_eq!(system.bounds, vec![7,5,4,4,3,3]);
+
+		// "... a total of 10 presses"
+		assert_eq!(system.fewest_presses(), Some(10));
+
+		// No presses make a counter odd while another stays at zero
+		let machine = read("[.#] (0,1) {1,0}").unwrap();
+		assert_eq!(JoltageSystem::new(&machine).and_then(|s| s.fewest_presses()), None);
+
+		let machine = read("[.#] (0,1) (0) {3,1}").unwrap();
+		assert_eq!(JoltageSystem::new(&machine).and_then(|s| s.fewest_presses()), Some(3));
+
+		let error = Part2::try_solve("[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}\n[.#] (0,1) {1,0}").unwrap_err();
+		assert!(matches!(error, AppError::Parse(ParseError { line: 2, .. })));
 	}
 
 	#[test]
 	fn test_example() {
 
 		let input = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}";
-		let machine = parser::machine(input).unwrap();
+		let machine = read(input).unwrap();
 
 		// "You could press the first three buttons once each, a total of 3 button presses."
 
 		let seq = machine.state_iter(0..3);
 		assert_eq!(seq.last().unwrap(), machine.target);
 
-		// assert_err!(dbg!(machine.try_press_btn(0)));
-		// assert_err!(dbg!(machine.try_press_btn(1)));
-		// assert_ok!(dbg!(machine.try_press_btn(2)));
+		let mut operated = Machine::new(&machine);
+
+		operated.press(0);
+		assert!(!operated.is_lit());
+		operated.press(1);
+		assert!(!operated.is_lit());
+		operated.press(2);
+		assert!(operated.is_lit());
 
-		// machine.reset();
+		operated.reset();
 
-		// assert_eq!(machine.current_state,MachineState(0));
-		// assert_eq!(machine.target_state,MachineState(0b0110));
+		assert_eq!(operated.state(),MachineState(0));
+		assert!(operated.history().is_empty());
 
 		// "You could press (1,3) once, (2,3) once, and (0,1) twice, a total of 4 button presses."
 		// NOTICE: We don't really need the last two because pressing a button twice does nothing
@@ -273,16 +790,115 @@ use super::*;
 	}
 
 	#[test]
-	fn test_part1() {
+	fn test_machine() {
 
-		let actual = Part1::solve(EXAMPLE_INPUT).to_string();
-		let expected = "7";
+		let input = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}";
+		let description = read(input).unwrap();
+		let mut machine = Machine::new(&description);
 
-		assert_eq!(actual,expected);
+		assert_eq!(machine.press(4), Some(MachineState(0b0101)));
+		assert_eq!(machine.press(6), None);
+		assert_eq!(machine.press(5), Some(MachineState(0b0110)));
+
+		assert_eq!(machine.history(), &[4,5]);
+		assert_eq!(machine.to_string(), "[.##.]");
+
+		let expected = indoc! {"
+			[....]
+			[#.#.] (0,2)
+			[.##.] (0,1)"
+		};
+
+		assert_eq!(machine.trace(), expected);
+	}
+
+	#[test]
+	fn test_explain() {
+
+		let input = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}";
+		let explanation = Part1::explain_with(input, &Lights::default()).unwrap().unwrap();
+
+		let expected = indoc! {"
+			Machine 1: 2 presses, (1,3) (2,3)
+			[....]
+			[.#.#] (1,3)
+			[.##.] (2,3)"
+		};
+
+		assert_eq!(explanation, expected);
+	}
+
+	#[test]
+	fn test_width() {
+
+		let wide = MachineState::<u128>::default().toggle_bit(100);
+		assert_eq!(wide, MachineState(1u128 << 100));
+
+		let input = "[.....................#] (0,21) (21) {0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,1}";
+		let wide = machine_of::<u32>(0, input).unwrap();
+
+		assert_eq!(wide.buttons[0], Button(1u32 << 21 | 1));
+		assert_eq!(find_fewest_presses(&wide).map(u32::count_ones), Some(1));
+
+		// Too many lights for 16 bits
+		let error = read(input).unwrap_err();
+		assert_eq!(error.expected, "at most 16 lights, the bitset width, not 22");
+
+		// A button wired past the lights
+		let error = read("[.#] (0) (1,3) {1,1}").unwrap_err();
+		assert_eq!((error.line,error.column), (1,13));
+		assert_eq!(error.expected, "a light index below 2, within a bitset of width 16, not 3");
+
+		// A counter missing
+		let error = read("[.#] (0) (1) {1}").unwrap_err();
+		assert_eq!(error.column, 14);
+		assert_eq!(error.expected, "2 joltage counters, one per light, not 1");
+
+		// Still reporting malformed lines
+		assert!(read("[.#] (0) (1) {1,1").is_err());
+	}
+
+	#[test]
+	fn test_fewest_presses() {
+
+		let input = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}";
+		let machine = read(input).unwrap();
+
+		let presses = find_fewest_presses(&machine).unwrap();
+
+		assert_eq!(presses.count_ones(), 2);
+		assert_eq!(machine.state_iter(pressed(presses)).last().unwrap(), machine.target);
+
+		// Both agree on every example machine, and on those whose lights should all stay off
+		let off = ["[..] (0) (1) {0,0}", "[...] (0,1) (1,2) (0,1) {0,0,0}"];
+		for line in EXAMPLE_INPUT.lines().chain(off) {
+			let machine = read(line).unwrap();
+			let fewest = find_fewest_presses(&machine).unwrap().count_ones() as usize;
+			assert_eq!(fewest, find_shortest_activation_seq(&machine).unwrap().count());
+		}
+
+		// No button lights the second light
+		let machine = read("[.#] (0) {1,0}").unwrap();
+		assert_eq!(find_fewest_presses(&machine), None);
+		assert!(find_shortest_activation_seq(&machine).is_none());
+
+		for method in [Method::Elimination, Method::Powerset] {
+			let error = Part1::try_solve_with("[.#] (0) {1,0}", &Lights { method }).unwrap_err();
+			assert!(matches!(error, AppError::Parse(ParseError { line: 1, .. })));
+		}
+	}
+
+	// EXAMPLES
+
+	examples! {
+		part1: Part1 => EXAMPLE_INPUT => "7",
+		part1_powerset: Part1(Lights { method: Method::Powerset }) => EXAMPLE_INPUT => "7",
+		part2: Part2 => EXAMPLE_INPUT => "33",
 	}
 
 	// SOLUTIONS
 
 	submit! { Part1 }
+	submit! { Part2 }
 
 }
@@ -0,0 +1,202 @@
+//! Cellular automata over a [Grid], re-evaluating only the cells next to changes
+
+use std::{collections::HashMap, hash::{DefaultHasher, Hash, Hasher}};
+
+use super::*;
+
+/// How a run of an [Automaton] ended
+#[derive(Debug,Clone,Copy,PartialEq,Eq)]
+pub enum Ending {
+	/// No cell changes any more, since the given generation
+	Fixpoint { generation: usize },
+	/// The state at `start` repeats every `period` generations
+	Cycle { start: usize, period: usize },
+	/// The generation limit was reached first
+	Limit,
+}
+
+/// Steps a grid with a rule applied to every cell at once.
+///
+/// The rule may only depend on the cell and its neighbours, under the given connectivity,
+/// so that only the cells around the latest changes need to be evaluated again.
+pub struct Automaton<T> {
+	grid: Grid<T>,
+	connectivity: Connectivity,
+	/// Cells to evaluate in the next generation
+	frontier: Vec<Location>,
+	queued: Grid<bool>,
+	/// Changed cells, by generation
+	changes: Vec<usize>,
+}
+
+impl<T:Clone+PartialEq> Automaton<T> {
+
+	pub fn new(grid:Grid<T>, connectivity:Connectivity) -> Self {
+		let frontier = grid.enumerate().map(|(xy,_)| xy.into()).collect();
+		let queued = grid.filled_like(true);
+		Self { grid, connectivity, frontier, queued, changes: vec![] }
+	}
+
+	pub fn grid(&self) -> &Grid<T> {
+		&self.grid
+	}
+
+	pub fn into_grid(self) -> Grid<T> {
+		self.grid
+	}
+
+	/// The generations stepped so far
+	pub fn generation(&self) -> usize {
+		self.changes.len()
+	}
+
+	/// How many cells changed, by generation
+	pub fn changes(&self) -> &[usize] {
+		&self.changes
+	}
+
+	/// Steps a generation, with `rule` giving the next state of a cell.
+	/// Returns how many cells changed.
+	pub fn step(&mut self, rule:impl Fn(&Grid<T>,Location) -> T) -> usize {
+		self.advance(rule).len()
+	}
+
+	/// Steps a generation, returning the changed cells with their previous states
+	fn advance(&mut self, rule:impl Fn(&Grid<T>,Location) -> T) -> Vec<(Location,T)> {
+
+		let mut updates = self.frontier.drain(..)
+			.filter_map(|loc| {
+				self.queued[loc] = false;
+				let next = rule(&self.grid, loc);
+				(next != self.grid[loc]).then_some((loc,next))
+			})
+			.collect_vec();
+
+		for (loc,next) in updates.iter_mut() {
+			std::mem::swap(&mut self.grid[*loc], next);
+		}
+
+		for &(loc,_) in updates.iter() {
+			for n in std::iter::once(loc).chain(self.grid.neighbours(loc, self.connectivity)) {
+				if !self.queued[n] {
+					self.queued[n] = true;
+					self.frontier.push(n);
+				}
+			}
+		}
+
+		self.changes.push(updates.len());
+
+		updates
+	}
+
+	/// Steps until nothing changes, a state repeats, or `limit` more generations were stepped
+	pub fn run(&mut self, rule:impl Fn(&Grid<T>,Location) -> T, limit:usize) -> Ending where T:Hash {
+
+		// States are told apart by the sum of the hashes of their cells,
+		// kept up to date with the changes alone
+		let hash = |loc:Location, cell:&T| {
+			let mut hasher = DefaultHasher::new();
+			(loc.x,loc.y,cell).hash(&mut hasher);
+			hasher.finish()
+		};
+
+		let mut fingerprint = self.grid.enumerate()
+			.fold(0u64, |sum,(xy,cell)| sum.wrapping_add(hash(xy.into(),cell)));
+
+		let first = self.generation();
+		let mut seen:HashMap<u64,Vec<usize>> = HashMap::from([(fingerprint, vec![first])]);
+
+		// The previous states of the changed cells, by generation since the first,
+		// to recover the earlier states when their fingerprints match
+		let mut undo:Vec<Vec<(Location,T)>> = vec![];
+
+		while self.generation() - first < limit {
+
+			let updates = self.advance(&rule);
+
+			if updates.is_empty() {
+				return Ending::Fixpoint { generation: self.generation() - 1 }
+			}
+
+			for (loc,previous) in updates.iter() {
+				fingerprint = fingerprint
+					.wrapping_sub(hash(*loc,previous))
+					.wrapping_add(hash(*loc,&self.grid[*loc]));
+			}
+
+			undo.push(updates);
+
+			let candidates = seen.entry(fingerprint).or_default();
+
+			// Fingerprints may collide, so the states are compared before reporting a cycle
+			let repeated = candidates.iter().copied().find(|&start| {
+				let mut earlier = self.grid.clone();
+				for (loc,previous) in undo[start-first..].iter().rev().flatten() {
+					earlier[*loc] = previous.clone();
+				}
+				earlier == self.grid
+			});
+
+			if let Some(start) = repeated {
+				return Ending::Cycle { start, period: self.generation() - start }
+			}
+
+			candidates.push(self.generation());
+		}
+
+		Ending::Limit
+	}
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	/// Conway's game of life
+	fn life(grid:&Grid<u8>, loc:Location) -> u8 {
+		let alive = grid.neighbours8(loc).filter(|&n| grid[n] == b'#').count();
+		match (grid[loc], alive) {
+			(b'#', 2..=3) | (_, 3) => b'#',
+			_ => b'.',
+		}
+	}
+
+	#[test]
+	fn test_cycle() {
+
+		let blinker:Grid<u8> = ".....\n..#..\n..#..\n..#..\n.....\n".parse().unwrap();
+		let mut automaton = Automaton::new(blinker.clone(), Connectivity::Eight);
+
+		assert_eq!(automaton.run(life, 10), Ending::Cycle { start: 0, period: 2 });
+		assert_eq!(automaton.changes(), [4,4]);
+		assert_eq!(automaton.grid(), &blinker);
+
+		// Runs may start after some generations were stepped
+		let mut automaton = Automaton::new(blinker, Connectivity::Eight);
+		assert_eq!(automaton.step(life), 4);
+		assert_eq!(automaton.run(life, 10), Ending::Cycle { start: 1, period: 2 });
+
+		// The limit counts the generations of the run alone
+		let mut automaton = Automaton::new(automaton.into_grid(), Connectivity::Eight);
+		automaton.step(life);
+		assert_eq!(automaton.run(life, 1), Ending::Limit);
+		assert_eq!(automaton.run(life, 2), Ending::Cycle { start: 2, period: 2 });
+		assert_eq!(automaton.generation(), 4);
+	}
+
+	#[test]
+	fn test_fixpoint() {
+
+		let block:Grid<u8> = "....\n.##.\n.#..\n....\n".parse().unwrap();
+		let mut automaton = Automaton::new(block, Connectivity::Eight);
+
+		assert_eq!(automaton.run(life, 10), Ending::Fixpoint { generation: 1 });
+		assert_eq!(automaton.changes(), [1,0]);
+		assert_eq!(automaton.grid().to_string(), "....\n.##.\n.##.\n....\n");
+
+		let mut automaton = Automaton::new(automaton.into_grid(), Connectivity::Eight);
+		assert_eq!(automaton.run(life, 0), Ending::Limit);
+	}
+}
@@ -0,0 +1,129 @@
+//! Connected-component labelling of a [Grid]
+
+use super::*;
+
+/// A connected region of cells
+#[derive(Debug,Clone,PartialEq,Eq)]
+pub struct Region {
+	pub id: GroupId,
+	/// The number of cells
+	pub area: usize,
+	/// The number of cell sides not shared with another cell of the region
+	pub perimeter: usize,
+	/// The top left and bottom right cells of the bounding box
+	pub bounds: (Location,Location),
+}
+
+/// The cells of a grid labelled by region, and the regions found
+#[derive(Debug,Clone)]
+pub struct Components {
+	/// [GroupId::None] for the cells outside any region
+	pub labels: Grid<GroupId>,
+	/// By id, starting with id 1
+	pub regions: Vec<Region>,
+}
+
+impl Components {
+
+	pub fn label(&self, loc:Location) -> GroupId {
+		self.labels[loc]
+	}
+
+	pub fn region(&self, id:GroupId) -> Option<&Region> {
+		let index = u16::from(id).checked_sub(1)?;
+		self.regions.get(index as usize)
+	}
+}
+
+impl<T> Grid<T> {
+
+	/// Finds the regions of adjacent cells satisfying `predicate`
+	pub fn components(&self, predicate:impl Fn(&T) -> bool, connectivity:Connectivity) -> Components {
+
+		let mut labels = self.filled_like(GroupId::None);
+		let mut regions:Vec<Region> = vec![];
+
+		for (xy,item) in self.enumerate() {
+
+			let start:Location = xy.into();
+
+			if labels[start] != GroupId::None || !predicate(item) {
+				continue
+			}
+
+			let id = u16::try_from(regions.len() + 1)
+				.expect("There should be fewer regions than group ids")
+				.into();
+
+			let mut region = Region { id, area: 0, perimeter: 0, bounds: (start,start) };
+
+			labels[start] = id;
+			let mut stack = vec![start];
+
+			while let Some(at) = stack.pop() {
+
+				region.area += 1;
+
+				let (min,max) = &mut region.bounds;
+				*min = (min.x.min(at.x), min.y.min(at.y)).into();
+				*max = (max.x.max(at.x), max.y.max(at.y)).into();
+
+				for next in self.neighbours(at, connectivity) {
+					if labels[next] == GroupId::None && predicate(&self[next]) {
+						labels[next] = id;
+						stack.push(next);
+					}
+				}
+			}
+
+			regions.push(region);
+		}
+
+		// Region cells are labelled by now, so their shared sides can be counted
+		for (xy,&id) in labels.enumerate() {
+			if let GroupId::Some(n) = id {
+				let shared = self.neighbours4(xy.into()).filter(|&n| labels[n] == id).count();
+				regions[n.get() as usize - 1].perimeter += 4 - shared;
+			}
+		}
+
+		Components { labels, regions }
+	}
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	#[test]
+	fn test_components() {
+
+		let grid:Grid<u8> = indoc! {
+			"
+			##..
+			#..#
+			..#.
+			"
+		}.parse().unwrap();
+
+		let is_wall = |&b:&u8| b == b'#';
+
+		let four = grid.components(is_wall, Connectivity::Four);
+
+		assert_eq!(four.regions.len(), 3);
+		assert_eq!(four.label((1,1).into()), GroupId::None);
+
+		let corner = four.region(four.label((0,0).into())).unwrap();
+		assert_eq!((corner.area,corner.perimeter), (3,8));
+		assert_eq!(corner.bounds, ((0,0).into(),(1,1).into()));
+
+		let eight = grid.components(is_wall, Connectivity::Eight);
+
+		assert_eq!(eight.regions.len(), 2);
+
+		let diagonal = eight.region(eight.label((3,1).into())).unwrap();
+		assert_eq!((diagonal.area,diagonal.perimeter), (2,8));
+		assert_eq!(diagonal.bounds, ((2,1).into(),(3,2).into()));
+	}
+}
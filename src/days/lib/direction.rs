@@ -0,0 +1,95 @@
+//! Directions on a plane, with `y` growing downwards as in the puzzle inputs
+
+use super::*;
+
+/// The orthogonal directions
+#[derive(Debug,Clone,Copy,PartialEq,Eq,Hash,PartialOrd,Ord)]
+pub enum Direction {
+	Up,
+	Right,
+	Down,
+	Left,
+}
+
+impl Direction {
+
+	/// Clockwise, from up
+	pub const ALL:[Self;4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+	pub const fn offset(self) -> Point2<isize> {
+		match self {
+			Self::Up    => Point([ 0,-1]),
+			Self::Right => Point([ 1, 0]),
+			Self::Down  => Point([ 0, 1]),
+			Self::Left  => Point([-1, 0]),
+		}
+	}
+
+	pub const fn turn_right(self) -> Self {
+		match self {
+			Self::Up    => Self::Right,
+			Self::Right => Self::Down,
+			Self::Down  => Self::Left,
+			Self::Left  => Self::Up,
+		}
+	}
+
+	pub const fn turn_left(self) -> Self {
+		self.turn_right().turn_right().turn_right()
+	}
+
+	pub const fn opposite(self) -> Self {
+		self.turn_right().turn_right()
+	}
+
+	/// Reads `^>v<` or `URDL`
+	pub fn from_byte(b:u8) -> Option<Self> {
+		match b {
+			b'^' | b'U' => Some(Self::Up),
+			b'>' | b'R' => Some(Self::Right),
+			b'v' | b'D' => Some(Self::Down),
+			b'<' | b'L' => Some(Self::Left),
+			_ => None,
+		}
+	}
+}
+
+/// The orthogonal and diagonal directions, named by compass points with north up
+#[derive(Debug,Clone,Copy,PartialEq,Eq,Hash,PartialOrd,Ord)]
+pub enum Direction8 {
+	N, NE, E, SE, S, SW, W, NW,
+}
+
+impl Direction8 {
+
+	/// Clockwise, from north
+	pub const ALL:[Self;8] = [Self::N, Self::NE, Self::E, Self::SE, Self::S, Self::SW, Self::W, Self::NW];
+
+	pub const fn offset(self) -> Point2<isize> {
+		match self {
+			Self::N  => Point([ 0,-1]),
+			Self::NE => Point([ 1,-1]),
+			Self::E  => Point([ 1, 0]),
+			Self::SE => Point([ 1, 1]),
+			Self::S  => Point([ 0, 1]),
+			Self::SW => Point([-1, 1]),
+			Self::W  => Point([-1, 0]),
+			Self::NW => Point([-1,-1]),
+		}
+	}
+
+	pub const fn is_diagonal(self) -> bool {
+		matches!(self, Self::NE | Self::SE | Self::SW | Self::NW)
+	}
+}
+
+impl From<Direction> for Direction8 {
+	fn from(d: Direction) -> Self {
+		match d {
+			Direction::Up    => Self::N,
+			Direction::Right => Self::E,
+			Direction::Down  => Self::S,
+			Direction::Left  => Self::W,
+		}
+	}
+}
@@ -0,0 +1,43 @@
+//! Labels for the groups found in graphs and grids
+
+use std::num::NonZero;
+
+/// The group something belongs to, if any, numbered from 1
+#[derive(Debug,Clone,Copy,Eq,PartialEq,Hash)]
+pub enum GroupId {
+	None,
+	Some(NonZero<u16>)
+}
+
+impl Ord for GroupId {
+	fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+		let a:u16 = (*self).into();
+		let b:u16 = (*other).into();
+		a.cmp(&b)
+	}
+}
+
+impl PartialOrd for GroupId {
+	fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl From<u16> for GroupId {
+	fn from(value: u16) -> Self {
+		match value {
+			0 => Self::None,
+			// SAFETY: Value 0 has been excluded in the previous match arm.
+			v => unsafe { Self::Some(NonZero::new_unchecked(v)) }
+		}
+	}
+}
+
+impl From<GroupId> for u16 {
+	fn from(val: GroupId) -> Self {
+		match val {
+			GroupId::None => 0,
+			GroupId::Some(v) => v.get()
+		}
+	}
+}
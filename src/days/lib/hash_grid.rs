@@ -0,0 +1,148 @@
+//! A sparse grid over an unbounded plane
+
+use std::collections::{HashMap, hash_map};
+
+use super::*;
+
+/// Only the occupied cells of an unbounded plane, by their signed coordinates
+#[derive(Debug,Clone,PartialEq,Eq)]
+pub struct HashGrid<T> {
+	cells: HashMap<Point2<isize>,T>
+}
+
+impl<T> Default for HashGrid<T> {
+	fn default() -> Self {
+		Self { cells: HashMap::new() }
+	}
+}
+
+impl<T> HashGrid<T> {
+
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// The cells of a [Grid] for which `keep` holds, at the same coordinates
+	pub fn from_grid(grid:&Grid<T>, keep:impl Fn(&T) -> bool) -> Self where T:Clone {
+		grid.enumerate()
+			.filter(|(_,item)| keep(item))
+			.map(|((x,y),item)| (Point([x as isize, y as isize]), item.clone()))
+			.collect()
+	}
+
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.cells.len()
+	}
+
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.cells.is_empty()
+	}
+
+	pub fn get(&self, at:Point2<isize>) -> Option<&T> {
+		self.cells.get(&at)
+	}
+
+	pub fn get_mut(&mut self, at:Point2<isize>) -> Option<&mut T> {
+		self.cells.get_mut(&at)
+	}
+
+	pub fn contains(&self, at:Point2<isize>) -> bool {
+		self.cells.contains_key(&at)
+	}
+
+	/// Occupies a cell, returning its previous content
+	pub fn insert(&mut self, at:Point2<isize>, item:T) -> Option<T> {
+		self.cells.insert(at, item)
+	}
+
+	/// Empties a cell, returning its content
+	pub fn remove(&mut self, at:Point2<isize>) -> Option<T> {
+		self.cells.remove(&at)
+	}
+
+	/// The occupied cells, in no particular order
+	pub fn iter(&self) -> hash_map::Iter<'_,Point2<isize>,T> {
+		self.cells.iter()
+	}
+
+	/// The top left and bottom right corners of the occupied cells
+	pub fn bounds(&self) -> Option<(Point2<isize>,Point2<isize>)> {
+		let (xs,ys):(Vec<_>,Vec<_>) = self.cells.keys().map(|p| (p.x(),p.y())).unzip();
+		let (min_x,max_x) = xs.into_iter().minmax().into_option()?;
+		let (min_y,max_y) = ys.into_iter().minmax().into_option()?;
+		Some((Point([min_x,min_y]),Point([max_x,max_y])))
+	}
+
+	/// The occupied orthogonal neighbours of a cell
+	pub fn neighbours4(&self, at:Point2<isize>) -> impl Iterator<Item=(Point2<isize>,&T)> {
+		Direction::ALL.into_iter()
+			.map(move |d| at + d.offset())
+			.filter_map(|p| self.get(p).map(|item| (p,item)))
+	}
+
+	/// The occupied orthogonal and diagonal neighbours of a cell
+	pub fn neighbours8(&self, at:Point2<isize>) -> impl Iterator<Item=(Point2<isize>,&T)> {
+		Direction8::ALL.into_iter()
+			.map(move |d| at + d.offset())
+			.filter_map(|p| self.get(p).map(|item| (p,item)))
+	}
+}
+
+impl<T> FromIterator<(Point2<isize>,T)> for HashGrid<T> {
+	fn from_iter<I: IntoIterator<Item=(Point2<isize>,T)>>(iter: I) -> Self {
+		Self { cells: iter.into_iter().collect() }
+	}
+}
+
+impl<T> Index<Point2<isize>> for HashGrid<T> {
+	type Output = T;
+
+	fn index(&self, at: Point2<isize>) -> &Self::Output {
+		self.get(at).unwrap_or_else(|| panic!("({at}) should be occupied"))
+	}
+}
+
+/// Renders the bounding box of the occupied cells, with `.` for the empty ones
+impl<T:Copy+Into<u8>> Display for HashGrid<T> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+
+		let Some((min,max)) = self.bounds() else {
+			return Ok(())
+		};
+
+		for y in min.y()..=max.y() {
+			let row = (min.x()..=max.x())
+				.map(|x| self.get(Point([x,y])).map_or(b'.', |&c| c.into()))
+				.collect_vec();
+			writeln!(f,"{}",String::from_utf8_lossy(&row))?;
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	#[test]
+	fn test_hash_grid() {
+
+		let grid:Grid<u8> = "#.\n.#\n".parse().unwrap();
+		let mut sparse = HashGrid::from_grid(&grid, |&b| b == b'#');
+
+		assert_eq!(sparse.len(), 2);
+		assert_eq!(sparse.neighbours8(Point([0,0])).count(), 1);
+		assert_eq!(sparse.neighbours4(Point([0,0])).count(), 0);
+
+		sparse.insert(Point([-2,-1]), b'@');
+
+		assert_eq!(sparse.bounds(), Some((Point([-2,-1]),Point([1,1]))));
+		assert_eq!(sparse.to_string(), "@...\n..#.\n...#\n");
+		assert_eq!(sparse.remove(Point([-2,-1])), Some(b'@'));
+		assert!(!sparse.contains(Point([-2,-1])));
+	}
+}
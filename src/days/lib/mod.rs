@@ -0,0 +1,421 @@
+#![allow(dead_code)]
+
+use std::ops::{Index, IndexMut};
+
+use super::*;
+
+pub trait IntoInner<T> {
+	fn into_inner(self) -> T;
+}
+
+mod location {
+
+	use super::{Direction, Point2, Size};
+
+	#[derive(Debug,Clone,Copy,PartialEq,Eq)]
+	pub struct Location {
+		pub x:usize,
+		pub y:usize,
+	}
+
+	impl From<(usize,usize)> for Location {
+		fn from((x,y): (usize,usize)) -> Self {
+			Self { x, y }
+		}
+	}
+
+	impl From<Location> for (usize,usize) {
+			fn from(loc: Location) -> Self {
+				(loc.x,loc.y)
+			}
+	}
+
+	impl Location {
+
+			pub fn down_unchecked(self) -> Self {
+				Self { y: self.y+1, ..self }
+			}
+
+			pub fn up_unchecked(self) -> Self {
+				Self { y: self.y-1, ..self }
+			}
+
+			pub fn right_unchecked(self) -> Self {
+				Self { x: self.x+1, ..self }
+			}
+
+			pub fn left_unchecked(self) -> Self {
+				Self { x: self.x-1, ..self }
+			}
+
+			/// Moves by an offset, unless that leaves a grid of the given size
+			pub fn checked_offset(self, offset:impl Into<Point2<isize>>, size:Size) -> Option<Self> {
+				let offset = offset.into();
+				let moved = Self {
+					x: self.x.checked_add_signed(offset.x())?,
+					y: self.y.checked_add_signed(offset.y())?,
+				};
+				size.contains(moved).then_some(moved)
+			}
+
+			pub fn checked_step(self, direction:Direction, size:Size) -> Option<Self> {
+				self.checked_offset(direction.offset(), size)
+			}
+
+			pub fn checked_down(self, size:Size) -> Option<Self> {
+				self.checked_offset((0,1), size)
+			}
+
+			pub fn checked_up(self, size:Size) -> Option<Self> {
+				self.checked_offset((0,-1), size)
+			}
+
+			pub fn checked_right(self, size:Size) -> Option<Self> {
+				self.checked_offset((1,0), size)
+			}
+
+			pub fn checked_left(self, size:Size) -> Option<Self> {
+				self.checked_offset((-1,0), size)
+			}
+	}
+
+}
+
+pub use location::*;
+
+mod size {
+
+	use super::Location;
+
+	pub trait HasSize {
+		fn size(&self)->Size;
+	}
+
+	#[derive(Debug,Clone,Copy,PartialEq,Eq,Hash)]
+	pub struct Size {
+		pub width:usize,
+		pub height:usize,
+	}
+
+	impl From<(usize,usize)> for Size {
+		fn from(value: (usize,usize)) -> Self {
+			Self { width: value.0, height: value.1 }
+		}
+	}
+
+	impl Size {
+
+		pub fn contains(&self, loc:Location) -> bool {
+			loc.x < self.width && loc.y < self.height
+		}
+	}
+}
+
+pub use size::*;
+
+use num::{Integer, integer::div_rem};
+
+#[inline]
+fn rem_div<T:Integer>(a:T,b:T) -> (T,T) {
+	let (div,rem) = div_rem(a,b);
+	(rem,div)
+}
+
+/// A grid that could not be built, or read from text
+#[derive(Debug,Clone,PartialEq,Eq)]
+pub enum GridError {
+	/// There are no rows, or the first one is empty.
+	/// Grids always have some width and height.
+	Empty,
+	/// A character that is not a valid cell
+	BadCell { at: Location, found: char, row: String },
+	/// A row whose width differs from the first one.
+	/// `row` is the offending row when read from text, and empty otherwise.
+	Ragged { y: usize, width: usize, expected: usize, row: String },
+	/// The cells do not fill whole rows
+	Uneven { len: usize, width: usize },
+}
+
+impl Display for GridError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			GridError::Empty => f.write_str("empty grid"),
+			GridError::BadCell { at, found, .. } => write!(f,"invalid cell `{found}` at ({},{})",at.x,at.y),
+			GridError::Ragged { y, width, expected, .. } => write!(f,"row {y} has width {width} instead of {expected}"),
+			GridError::Uneven { len, width } => write!(f,"{len} cells do not fill rows of width {width}"),
+		}
+	}
+}
+
+impl std::error::Error for GridError {}
+
+/// Which cells are adjacent
+#[derive(Debug,Clone,Copy,PartialEq,Eq)]
+pub enum Connectivity {
+	/// Orthogonally
+	Four,
+	/// Orthogonally or diagonally
+	Eight,
+}
+
+#[derive(Debug,Clone,PartialEq,Eq,Hash)]
+pub struct Grid<T> {
+	pub size: Size,
+	items: Vec<T>
+}
+
+impl<T> size::HasSize for Grid<T> {
+	fn size(&self)->Size {
+		self.size
+	}
+}
+
+impl<T> Grid<T> {
+
+	/// Panics unless the items fill whole rows of the given width, see [Grid::try_new]
+	pub fn new(width:usize,items:Vec<T>) -> Self {
+		Self::try_new(width, items).unwrap_or_else(|e| panic!("Invalid grid: {e}"))
+	}
+
+	/// The grid with the items in row order, which should fill at least one whole row
+	pub fn try_new(width:usize,items:Vec<T>) -> Result<Self,GridError> {
+		if width == 0 || items.is_empty() {
+			return Err(GridError::Empty)
+		}
+		if !items.len().is_multiple_of(width) {
+			return Err(GridError::Uneven { len: items.len(), width })
+		}
+		let size = (width, items.len() / width).into();
+		Ok(Self { size, items })
+	}
+
+	/// The grid with the given rows, which should all have the same width
+	pub fn from_rows<R:IntoIterator<Item=T>>(rows:impl IntoIterator<Item=R>) -> Result<Self,GridError> {
+
+		let mut items = vec![];
+		let mut width = None;
+
+		for (y,row) in rows.into_iter().enumerate() {
+			let start = items.len();
+			items.extend(row);
+			let row_width = items.len() - start;
+			match width {
+				None => width = Some(row_width),
+				Some(expected) if expected != row_width => {
+					return Err(GridError::Ragged { y, width: row_width, expected, row: String::new() })
+				},
+				_ => (),
+			}
+		}
+
+		Self::try_new(width.ok_or(GridError::Empty)?, items)
+	}
+
+	/// The grid of the given size with every item set to `item`, which should not be empty
+	pub fn filled(size:Size, item:T) -> Result<Self,GridError> where T:Clone {
+		Self::try_new(size.width, vec![item;size.width*size.height])
+	}
+
+	/// The grid with `f` giving the item at each location, which should not be empty
+	pub fn from_fn(size:Size, mut f:impl FnMut(Location) -> T) -> Result<Self,GridError> {
+		let items = (0..size.height)
+			.cartesian_product(0..size.width)
+			.map(|(y,x)| f((x,y).into()))
+			.collect();
+		Self::try_new(size.width, items)
+	}
+
+	/// A grid of the same size, with every item set to `item`
+	pub fn filled_like<U:Clone>(&self, item:U) -> Grid<U> {
+		Grid { size: self.size, items: vec![item;self.items.len()] }
+	}
+
+	/// The item at `loc`, if within the grid
+	pub fn get(&self, loc:impl Into<(usize,usize)>) -> Option<&T> {
+		let (x,y) = loc.into();
+		(x < self.size.width && y < self.size.height).then(|| &self.items[xy_to_i(self.stride(),(x,y))])
+	}
+
+	/// The item at `loc`, if within the grid
+	pub fn get_mut(&mut self, loc:impl Into<(usize,usize)>) -> Option<&mut T> {
+		let (x,y) = loc.into();
+		let stride = self.stride();
+		(x < self.size.width && y < self.size.height).then(|| &mut self.items[xy_to_i(stride,(x,y))])
+	}
+
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.items.len()
+	}
+
+	#[inline]
+	pub fn stride(&self) -> usize {
+		self.size.width
+	}
+
+	pub fn iter(&self) -> impl Iterator<Item=&T> {
+		self.items.iter()
+	}
+
+	pub fn iter_mut(&mut self) -> impl Iterator<Item=&mut T> {
+		self.items.iter_mut()
+	}
+
+	pub fn enumerate(&self) -> impl Iterator<Item=((usize,usize),&T)> {
+		self.items.iter().enumerate().map(|(idx,itm)| (i_to_xy(self.stride(), idx),itm))
+	}
+
+	/// The neighbours of `loc` within the grid
+	pub fn neighbours(&self, loc:Location, connectivity:Connectivity) -> impl Iterator<Item=Location> + use<T> {
+		match connectivity {
+			Connectivity::Four  => itertools::Either::Left(self.neighbours4(loc)),
+			Connectivity::Eight => itertools::Either::Right(self.neighbours8(loc)),
+		}
+	}
+
+	/// The up to 4 orthogonal neighbours of `loc` within the grid
+	pub fn neighbours4(&self, loc:Location) -> impl Iterator<Item=Location> + use<T> {
+		let size = self.size;
+		Direction::ALL.into_iter().filter_map(move |d| loc.checked_step(d, size))
+	}
+
+	/// The up to 8 orthogonal and diagonal neighbours of `loc` within the grid
+	pub fn neighbours8(&self, loc:Location) -> impl Iterator<Item=Location> + use<T> {
+		let size = self.size;
+		Direction8::ALL.into_iter().filter_map(move |d| loc.checked_offset(d.offset(), size))
+	}
+}
+
+impl<I:Eq+PartialEq> Grid<I> {
+
+	pub fn find_position(&self,item:I) -> Option<(usize,usize)> {
+		self.items.iter()
+			.find_position(|&i| *i == item)
+			.map(|(i,_)| i_to_xy(self.stride(), i))
+	}
+}
+
+#[inline]
+fn xy_to_i(stride:usize, (x,y):(usize,usize)) -> usize {
+	stride * y + x
+}
+
+#[inline]
+fn i_to_xy(stride:usize, i:usize) -> (usize,usize) {
+	rem_div(i, stride)
+}
+
+/// Panics outside the grid, see [Grid::get]
+impl<T,I:Into<(usize,usize)>> Index<I> for Grid<T> {
+	type Output = T;
+
+	fn index(&self, index: I) -> &Self::Output {
+		let (x,y) = index.into();
+		self.get((x,y)).unwrap_or_else(|| panic!("({x},{y}) is outside the grid"))
+	}
+}
+
+/// Panics outside the grid, see [Grid::get_mut]
+impl<T,I:Into<(usize,usize)>> IndexMut<I> for Grid<T> {
+	fn index_mut(&mut self, index: I) -> &mut Self::Output {
+		let (x,y) = index.into();
+		self.get_mut((x,y)).unwrap_or_else(|| panic!("({x},{y}) is outside the grid"))
+	}
+}
+
+mod point;
+pub use point::*;
+
+mod direction;
+pub use direction::*;
+
+mod hash_grid;
+pub use hash_grid::*;
+
+mod text;
+pub use text::*;
+
+mod search;
+pub use search::*;
+
+mod group;
+pub use group::*;
+
+mod components;
+pub use components::*;
+
+mod automaton;
+pub use automaton::*;
+
+mod visual;
+pub use visual::*;
+
+mod snapshot;
+pub use snapshot::*;
+
+mod views;
+pub use views::*;
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	#[test]
+	fn test_neighbours() {
+
+		let grid = Grid::new(3, (0..6).collect());
+		let values = |locs:Vec<Location>| locs.into_iter().map(|l| grid[l]).collect_vec();
+
+		assert_eq!(values(grid.neighbours4((0,0).into()).collect()), vec![1,3]);
+		assert_eq!(values(grid.neighbours4((1,1).into()).collect()), vec![1,5,3]);
+		assert_eq!(values(grid.neighbours8((2,0).into()).collect()), vec![5,4,1]);
+		assert_eq!(values(grid.neighbours8((1,0).into()).collect()), vec![2,5,4,3,0]);
+
+		let size = grid.size;
+		let corner:Location = (2,1).into();
+
+		assert_eq!(corner.checked_down(size), None);
+		assert_eq!(corner.checked_right(size), None);
+		assert_eq!(corner.checked_up(size), Some((2,0).into()));
+		assert_eq!(Location::from((0,0)).checked_left(size), None);
+	}
+
+	#[test]
+	fn test_constructors() {
+
+		assert_eq!(Grid::try_new(3, (0..5).collect()), Err(GridError::Uneven { len: 5, width: 3 }));
+		assert_eq!(Grid::<u8>::try_new(0, vec![]), Err(GridError::Empty));
+
+		let grid = Grid::from_rows([[0,1,2],[3,4,5]]).unwrap();
+
+		assert_eq!(grid, Grid::new(3, (0..6).collect()));
+		assert_eq!(Grid::from_fn((3,2).into(), |loc| loc.y * 3 + loc.x), Ok(grid.clone()));
+		assert_eq!(Grid::filled((2,1).into(), 7), Ok(Grid::new(2, vec![7,7])));
+		assert_eq!(Grid::filled((0,0).into(), 7), Err(GridError::Empty));
+		assert_eq!(Grid::from_fn((0,2).into(), |_| 7), Err(GridError::Empty));
+		assert_eq!(Grid::<u8>::try_new(3, vec![]), Err(GridError::Empty));
+		assert_eq!(Grid::filled((3,0).into(), 7), Err(GridError::Empty));
+
+		assert_eq!(grid.filled_like('.'), Grid::new(3, vec!['.';6]));
+
+		let ragged = Grid::from_rows(vec![vec![0,1],vec![2]]);
+		assert_eq!(ragged, Err(GridError::Ragged { y: 1, width: 1, expected: 2, row: String::new() }));
+		assert_eq!(Grid::<u8>::from_rows(Vec::<Vec<u8>>::new()), Err(GridError::Empty));
+	}
+
+	#[test]
+	fn test_get() {
+
+		let mut grid = Grid::new(3, (0..6).collect());
+
+		assert_eq!(grid.get((2,1)), Some(&5));
+		// Not wrapping around to the next row
+		assert_eq!(grid.get((3,0)), None);
+		assert_eq!(grid.get((0,2)), None);
+
+		*grid.get_mut((0,1)).unwrap() = 9;
+		assert_eq!(grid[(0,1)], 9);
+		assert_eq!(grid.get_mut((3,1)), None);
+	}
+}
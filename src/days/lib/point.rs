@@ -0,0 +1,230 @@
+//! Points with signed or unsigned coordinates, in any number of dimensions
+
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use num::{NumCast, Zero};
+
+use super::*;
+
+/// A point, or an offset between points, with `D` coordinates of type `N`
+#[derive(Debug,Clone,Copy,PartialEq,Eq,Hash,PartialOrd,Ord)]
+pub struct Point<N,const D:usize>(pub [N;D]);
+
+pub type Point2<N> = Point<N,2>;
+pub type Point3<N> = Point<N,3>;
+
+impl<N:Copy,const D:usize> Point<N,D> {
+
+	pub const fn new(coords:[N;D]) -> Self {
+		Self(coords)
+	}
+
+	pub fn x(&self) -> N {
+		self.0[0]
+	}
+
+	pub fn y(&self) -> N {
+		self.0[1]
+	}
+
+	/// Converts the coordinates to another type, if they fit
+	pub fn cast<M:NumCast>(self) -> Option<Point<M,D>> where N:NumCast {
+		let coords:Vec<M> = self.0.into_iter().map(M::from).collect::<Option<_>>()?;
+		coords.try_into().ok().map(Point)
+	}
+
+	fn zip_with(self, other:Self, f:impl Fn(N,N) -> N) -> Self {
+		Self(std::array::from_fn(|i| f(self.0[i],other.0[i])))
+	}
+}
+
+impl<N:Copy> Point<N,3> {
+
+	pub fn z(&self) -> N {
+		self.0[2]
+	}
+}
+
+impl<N:Copy+Zero,const D:usize> Point<N,D> {
+
+	pub fn origin() -> Self {
+		Self([N::zero();D])
+	}
+}
+
+impl<N:Copy+Zero,const D:usize> Default for Point<N,D> {
+	fn default() -> Self {
+		Self::origin()
+	}
+}
+
+/// Distances between points
+impl<N,const D:usize> Point<N,D>
+where N: Copy + Ord + Zero + Sub<Output=N> + Mul<Output=N>
+{
+	fn abs_diffs(self, other:Self) -> impl Iterator<Item=N> {
+		(0..D).map(move |i| {
+			let (a,b) = (self.0[i],other.0[i]);
+			if a > b { a - b } else { b - a }
+		})
+	}
+
+	/// The length of the shortest path moving orthogonally
+	pub fn manhattan(self, other:Self) -> N {
+		self.abs_diffs(other).fold(N::zero(), |a,b| a + b)
+	}
+
+	/// The length of the shortest path moving orthogonally or diagonally
+	pub fn chebyshev(self, other:Self) -> N {
+		self.abs_diffs(other).fold(N::zero(), N::max)
+	}
+
+	/// The squared straight line distance, which orders points like the distance does
+	pub fn euclidean_squared(self, other:Self) -> N {
+		self.abs_diffs(other).fold(N::zero(), |a,d| a + d * d)
+	}
+}
+
+impl Point2<isize> {
+
+	/// Wraps around a board of the given size, as on a torus
+	pub fn wrap(self, size:Size) -> Location {
+		let x = self.x().rem_euclid(size.width as isize);
+		let y = self.y().rem_euclid(size.height as isize);
+		(x as usize, y as usize).into()
+	}
+
+	/// The location on a board of the given size, if the point is on it
+	pub fn within(self, size:Size) -> Option<Location> {
+		let loc:Location = self.cast::<usize>()?.into();
+		size.contains(loc).then_some(loc)
+	}
+}
+
+impl<N:Copy+Add<Output=N>,const D:usize> Add for Point<N,D> {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		self.zip_with(rhs, N::add)
+	}
+}
+
+impl<N:Copy+Sub<Output=N>,const D:usize> Sub for Point<N,D> {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		self.zip_with(rhs, N::sub)
+	}
+}
+
+impl<N:Copy+Add<Output=N>,const D:usize> AddAssign for Point<N,D> {
+	fn add_assign(&mut self, rhs: Self) {
+		*self = *self + rhs;
+	}
+}
+
+impl<N:Copy+Sub<Output=N>,const D:usize> SubAssign for Point<N,D> {
+	fn sub_assign(&mut self, rhs: Self) {
+		*self = *self - rhs;
+	}
+}
+
+/// Scaling
+impl<N:Copy+Mul<Output=N>,const D:usize> Mul<N> for Point<N,D> {
+	type Output = Self;
+
+	fn mul(self, rhs: N) -> Self::Output {
+		Self(self.0.map(|n| n * rhs))
+	}
+}
+
+impl<N:Copy+Neg<Output=N>,const D:usize> Neg for Point<N,D> {
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		Self(self.0.map(N::neg))
+	}
+}
+
+impl<N,const D:usize> From<[N;D]> for Point<N,D> {
+	fn from(coords: [N;D]) -> Self {
+		Self(coords)
+	}
+}
+
+impl<N> From<(N,N)> for Point2<N> {
+	fn from((x,y): (N,N)) -> Self {
+		Self([x,y])
+	}
+}
+
+impl<N> From<(N,N,N)> for Point3<N> {
+	fn from((x,y,z): (N,N,N)) -> Self {
+		Self([x,y,z])
+	}
+}
+
+impl From<Location> for Point2<usize> {
+	fn from(loc: Location) -> Self {
+		Self([loc.x,loc.y])
+	}
+}
+
+impl From<Point2<usize>> for Location {
+	fn from(p: Point2<usize>) -> Self {
+		(p.x(),p.y()).into()
+	}
+}
+
+impl<N:Display,const D:usize> Display for Point<N,D> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		for (i,n) in self.0.iter().enumerate() {
+			if i > 0 {
+				f.write_str(",")?;
+			}
+			write!(f,"{n}")?;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	#[test]
+	fn test_arithmetic() {
+
+		let a:Point2<isize> = (1,-2).into();
+		let b:Point2<isize> = (-3,4).into();
+
+		assert_eq!(a + b, (-2,2).into());
+		assert_eq!(a - b, (4,-6).into());
+		assert_eq!(-a * 2, (-2,4).into());
+		assert_eq!(a.cast::<usize>(), None);
+		assert_eq!(Point([3isize,4]).cast::<usize>(), Some(Point([3,4])));
+	}
+
+	#[test]
+	fn test_distances() {
+
+		let a:Point3<usize> = (1,5,2).into();
+		let b:Point3<usize> = (4,1,2).into();
+
+		assert_eq!(a.manhattan(b), 7);
+		assert_eq!(a.chebyshev(b), 4);
+		assert_eq!(a.euclidean_squared(b), 25);
+		assert_eq!(a.to_string(), "1,5,2");
+	}
+
+	#[test]
+	fn test_wrap() {
+
+		let size:Size = (3,2).into();
+
+		assert_eq!(Point([-1isize,2]).wrap(size), (2,0).into());
+		assert_eq!(Point([-1isize,1]).within(size), None);
+		assert_eq!(Point([2isize,1]).within(size), Some((2,1).into()));
+	}
+}
@@ -0,0 +1,191 @@
+//! Path finding over a [Grid], moving orthogonally
+
+use std::{cmp::Reverse, collections::{BinaryHeap, VecDeque}};
+
+use super::*;
+
+/// The distances from a start location, and the way back to it
+#[derive(Debug,Clone)]
+pub struct Search {
+	start: Location,
+	distances: Grid<Option<usize>>,
+	previous: Grid<Option<Location>>,
+}
+
+impl Search {
+
+	fn new<T>(start:Location, grid:&Grid<T>) -> Self {
+		let mut distances = grid.filled_like(None);
+		distances[start] = Some(0);
+		Self { start, distances, previous: grid.filled_like(None) }
+	}
+
+	fn reach(&mut self, at:Location, from:Location, distance:usize) {
+		self.distances[at] = Some(distance);
+		self.previous[at] = Some(from);
+	}
+
+	pub fn start(&self) -> Location {
+		self.start
+	}
+
+	/// The distance from the start, if `to` was reached
+	pub fn distance(&self, to:Location) -> Option<usize> {
+		self.distances[to]
+	}
+
+	/// The locations from the start to `to`, both included, if `to` was reached
+	pub fn path(&self, to:Location) -> Option<Vec<Location>> {
+
+		self.distance(to)?;
+
+		let mut path = vec![to];
+
+		while let Some(prev) = self.previous[*path.last().unwrap()] {
+			path.push(prev);
+		}
+
+		path.reverse();
+
+		Some(path)
+	}
+
+	/// The reached locations, with their distances
+	pub fn reached(&self) -> impl Iterator<Item=(Location,usize)> {
+		self.distances.enumerate().filter_map(|(xy,d)| d.map(|d| (xy.into(),d)))
+	}
+}
+
+impl<T> Grid<T> {
+
+	/// Flood fills from `start`, stepping only onto cells that are `passable`,
+	/// with every step costing 1
+	pub fn bfs(&self, start:Location, passable:impl Fn(&T) -> bool) -> Search {
+
+		let mut search = Search::new(start, self);
+		let mut queue = VecDeque::from([start]);
+
+		while let Some(at) = queue.pop_front() {
+
+			let distance = search.distances[at].unwrap() + 1;
+
+			for next in self.neighbours4(at) {
+				if search.distances[next].is_none() && passable(&self[next]) {
+					search.reach(next, at, distance);
+					queue.push_back(next);
+				}
+			}
+		}
+
+		search
+	}
+
+	/// Finds the cheapest paths from `start` to everywhere,
+	/// with `cost` giving the cost of stepping between two cells, if possible at all
+	pub fn dijkstra(&self, start:Location, cost:impl Fn(&T,&T) -> Option<usize>) -> Search {
+		self.best_first(start, None, cost, |_| 0)
+	}
+
+	/// Finds the cheapest path from `start` to `goal`, guided by a `heuristic`
+	/// that should never overestimate the remaining cost.
+	/// Returns the cost and the path, both ends included.
+	pub fn astar(
+		&self,
+		start:Location,
+		goal:Location,
+		cost:impl Fn(&T,&T) -> Option<usize>,
+		heuristic:impl Fn(Location) -> usize
+	) -> Option<(usize,Vec<Location>)> {
+		let search = self.best_first(start, Some(goal), cost, heuristic);
+		Some((search.distance(goal)?, search.path(goal)?))
+	}
+
+	fn best_first(
+		&self,
+		start:Location,
+		goal:Option<Location>,
+		cost:impl Fn(&T,&T) -> Option<usize>,
+		heuristic:impl Fn(Location) -> usize
+	) -> Search {
+
+		let mut search = Search::new(start, self);
+
+		// Ordered by estimated total cost, then by location for determinism
+		let mut open = BinaryHeap::from([Reverse((heuristic(start), start.y, start.x))]);
+
+		while let Some(Reverse((_, y, x))) = open.pop() {
+
+			let at:Location = (x,y).into();
+
+			if Some(at) == goal {
+				break
+			}
+
+			let so_far = search.distances[at].unwrap();
+
+			for next in self.neighbours4(at) {
+
+				let Some(step) = cost(&self[at], &self[next]) else {
+					continue
+				};
+
+				let distance = so_far + step;
+
+				if search.distances[next].is_none_or(|d| distance < d) {
+					search.reach(next, at, distance);
+					open.push(Reverse((distance + heuristic(next), next.y, next.x)));
+				}
+			}
+		}
+
+		search
+	}
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	const MAZE:&str = indoc! {
+		"
+		S.#...
+		.##.#.
+		....#E
+		"
+	};
+
+	#[test]
+	fn test_bfs() {
+
+		let grid:Grid<u8> = MAZE.parse().unwrap();
+		let search = grid.bfs((0,0).into(), |&b| b != b'#');
+
+		assert_eq!(search.distance((5,2).into()), Some(11));
+		assert_eq!(search.distance((2,0).into()), None);
+		assert_eq!(search.reached().count(), 13);
+
+		let path = search.path((3,0).into()).unwrap();
+		assert_eq!(path.len(), 8);
+		assert_eq!((path[0],path[7]), ((0,0).into(),(3,0).into()));
+	}
+
+	#[test]
+	fn test_weighted() {
+
+		// Digits are the cost of stepping onto a cell
+		let grid:Grid<u8> = "1911\n1111\n9991\n".parse().unwrap();
+		let cost = |_:&u8, &to:&u8| Some((to - b'0') as usize);
+
+		let search = grid.dijkstra((0,0).into(), cost);
+		assert_eq!(search.distance((3,2).into()), Some(5));
+		assert_eq!(search.distance((2,0).into()), Some(4));
+
+		let manhattan = |loc:Location| Point2::from(loc).manhattan(Point([3,2]));
+		let (total,path) = grid.astar((0,0).into(), (3,2).into(), cost, manhattan).unwrap();
+
+		assert_eq!(total, 5);
+		assert_eq!(path.len(), 6);
+		assert_eq!(grid.astar((0,0).into(), (3,2).into(), |_,_| None, manhattan), None);
+	}
+}
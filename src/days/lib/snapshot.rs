@@ -0,0 +1,256 @@
+//! Grids saved as text, with their size and legend, and compared in tests:
+//!
+//! ```text
+//! # grid 3x2
+//! # legend . Empty
+//! # legend # Wall
+//! #..
+//! .#.
+//! ```
+
+use std::{fmt::Debug, path::{Path, PathBuf}};
+
+use super::*;
+
+/// Where [assert_snapshot] keeps the snapshots
+fn snapshot_path(name:&str) -> PathBuf {
+	PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+		.join("src/days/snapshots")
+		.join(format!("{name}.snap"))
+}
+
+impl<T:Copy+Into<u8>+Debug> Grid<T> {
+
+	/// The grid as text, preceded by its size and a legend of the cells in use
+	pub fn to_snapshot(&self) -> String {
+
+		let Size { width, height } = self.size;
+		let mut snapshot = format!("# grid {width}x{height}\n");
+
+		let legend = self.iter()
+			.map(|&item| (item.into(), item))
+			.unique_by(|&(b,_)| b)
+			.sorted_unstable_by_key(|&(b,_)| b);
+
+		for (b,item) in legend {
+			snapshot.push_str(&format!("# legend {} {item:?}\n", b as char));
+		}
+
+		snapshot.push_str(&self.to_string());
+		snapshot
+	}
+}
+
+impl<T:TryFrom<u8>> Grid<T> {
+
+	/// Reads a grid written by [Grid::to_snapshot]
+	pub fn from_snapshot(snapshot:&str) -> Result<Self,String> {
+
+		let (header, rows) = snapshot.split_once('\n').ok_or("Missing snapshot header")?;
+
+		let size = header.strip_prefix("# grid ")
+			.and_then(|s| s.split_once('x'))
+			.and_then(|(w,h)| Some(Size::from((w.parse().ok()?, h.parse().ok()?))))
+			.ok_or_else(|| format!("Malformed snapshot header `{header}`"))?;
+
+		let rows = rows.lines()
+			.skip_while(|l| l.starts_with("# legend "))
+			.join("\n");
+
+		let grid:Self = rows.parse().map_err(|e| format!("Malformed snapshot, {e}"))?;
+
+		if grid.size != size {
+			return Err(format!("Snapshot of size {}x{} has size {}x{}", size.width, size.height, grid.size.width, grid.size.height))
+		}
+
+		Ok(grid)
+	}
+}
+
+/// Compares a grid with the snapshot `src/days/snapshots/<name>.snap`,
+/// showing the differing cells on mismatch.
+///
+/// A missing snapshot fails too, so that it is not skipped unnoticed.
+/// Snapshots are written instead when `UPDATE_SNAPSHOTS` is set.
+#[track_caller]
+pub fn assert_snapshot<T:Copy+Into<u8>+Debug>(grid:&Grid<T>, name:&str) {
+	let update = std::env::var_os("UPDATE_SNAPSHOTS").is_some();
+	if let Err(diff) = compare_snapshot(&grid.to_snapshot(), &snapshot_path(name), update) {
+		panic!("Snapshot `{name}` differs\n{diff}");
+	}
+}
+
+/// Compares a snapshot with the one stored at `path`, unless updating
+fn compare_snapshot(actual:&str, path:&Path, update:bool) -> Result<(),String> {
+
+	if update {
+		std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+		std::fs::write(path, actual).unwrap_or_else(|e| panic!("Cannot write {}: {e}", path.display()));
+		return Ok(())
+	}
+
+	if !path.exists() {
+		return Err(format!("Missing {}, set UPDATE_SNAPSHOTS to write it", path.display()))
+	}
+
+	let expected = std::fs::read_to_string(path)
+		.unwrap_or_else(|e| panic!("Cannot read {}: {e}", path.display()));
+
+	if expected == actual {
+		Ok(())
+	} else {
+		Err(snapshot_diff(&expected, actual))
+	}
+}
+
+/// The header lines of a snapshot, its size then its legend, and the rows that follow,
+/// told apart by the same prefixes as [Grid::from_snapshot] since rows may start with `# ` too
+fn split_snapshot(snapshot:&str) -> (Vec<&str>, Vec<&str>) {
+
+	let mut lines = snapshot.lines().peekable();
+	let mut header = vec![];
+
+	if let Some(size) = lines.next_if(|l| l.starts_with("# grid ")) {
+		header.push(size);
+		header.extend(std::iter::from_fn(|| lines.next_if(|l| l.starts_with("# legend "))));
+	}
+
+	(header, lines.collect())
+}
+
+/// The expected and actual rows side by side, with `^` under the differing cells
+fn snapshot_diff(expected:&str, actual:&str) -> String {
+
+	let (expected_meta, expected_rows) = split_snapshot(expected);
+	let (actual_meta, actual_rows) = split_snapshot(actual);
+
+	let mut diff = String::new();
+
+	for line in expected_meta.iter().filter(|l| !actual_meta.contains(l)) {
+		diff.push_str(&format!("- {line}\n"));
+	}
+	for line in actual_meta.iter().filter(|l| !expected_meta.contains(l)) {
+		diff.push_str(&format!("+ {line}\n"));
+	}
+
+	let width = expected_rows.iter().map(|r| r.len()).chain(["expected".len()]).max().unwrap();
+	let mut cells = 0;
+
+	diff.push_str(&format!("  {:width$}   actual\n", "expected"));
+
+	for pair in expected_rows.iter().zip_longest(actual_rows.iter()) {
+
+		let (e,a) = pair.map_any(|e| *e, |a| *a).or("", "");
+		diff.push_str(&format!("  {e:width$}   {a}\n"));
+
+		let marks:String = e.bytes().zip_longest(a.bytes())
+			.map(|p| match p {
+				itertools::EitherOrBoth::Both(x,y) if x == y => ' ',
+				_ => { cells += 1; '^' },
+			})
+			.collect();
+
+		if marks.contains('^') {
+			diff.push_str(&format!("  {:width$}   {}\n", "", marks.trim_end()));
+		}
+	}
+
+	diff.push_str(&format!("{cells} cell(s) differ"));
+	diff
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	#[derive(Debug,Clone,Copy,PartialEq,Eq)]
+	enum Cell {
+		Empty,
+		Wall,
+	}
+
+	impl TryFrom<u8> for Cell {
+		type Error = ();
+
+		fn try_from(value: u8) -> Result<Self, Self::Error> {
+			match value {
+				b'.' => Ok(Self::Empty),
+				b'#' => Ok(Self::Wall),
+				_ => Err(())
+			}
+		}
+	}
+
+	impl From<Cell> for u8 {
+		fn from(value: Cell) -> Self {
+			match value {
+				Cell::Empty => b'.',
+				Cell::Wall  => b'#',
+			}
+		}
+	}
+
+	const SNAPSHOT:&str = indoc! {
+		"
+		# grid 3x2
+		# legend # Wall
+		# legend . Empty
+		#..
+		.#.
+		"
+	};
+
+	#[test]
+	fn test_round_trip() {
+
+		let grid:Grid<Cell> = Grid::from_snapshot(SNAPSHOT).unwrap();
+
+		assert_eq!(grid[(1,1)], Cell::Wall);
+		assert_eq!(grid.to_snapshot(), SNAPSHOT);
+
+		assert!(Grid::<Cell>::from_snapshot("# grid 2x2\n#.\n").is_err());
+		assert!(Grid::<Cell>::from_snapshot("#.\n").is_err());
+	}
+
+	#[test]
+	fn test_diff() {
+
+		let actual = SNAPSHOT.replace(".#.", "..#");
+		let diff = snapshot_diff(SNAPSHOT, &actual);
+
+		assert!(diff.ends_with("2 cell(s) differ"));
+		assert!(diff.contains("\n  .#.        ..#\n              ^^\n"));
+
+		// Rows starting with a wall and an empty cell are not part of the header
+		let expected = "# grid 2x2\n# legend   Empty\n# legend # Wall\n# \n  \n";
+		let actual = "# grid 2x2\n# legend   Empty\n# legend # Wall\n  \n# \n";
+		let diff = snapshot_diff(expected, actual);
+
+		assert!(diff.ends_with("2 cell(s) differ"));
+		assert!(!diff.contains("- # \n"));
+	}
+
+	#[test]
+	fn test_compare_snapshot() {
+
+		let path = std::env::temp_dir()
+			.join(format!("aoc_2025_snapshot_{}", std::process::id()))
+			.join("grid.snap");
+
+		let rotated = SNAPSHOT.replace("#..\n.#.", ".#.\n..#");
+
+		// Missing until written, then compared
+		assert!(compare_snapshot(SNAPSHOT, &path, false).is_err());
+		assert!(!path.exists());
+
+		assert_eq!(compare_snapshot(SNAPSHOT, &path, true), Ok(()));
+		assert_eq!(compare_snapshot(SNAPSHOT, &path, false), Ok(()));
+		assert!(compare_snapshot(&rotated, &path, false).is_err());
+
+		assert_eq!(compare_snapshot(&rotated, &path, true), Ok(()));
+		assert_eq!(compare_snapshot(&rotated, &path, false), Ok(()));
+
+		std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+	}
+}
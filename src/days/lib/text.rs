@@ -0,0 +1,112 @@
+//! Reading and rendering grids with one byte per cell
+
+use super::*;
+
+impl From<GridError> for ParseError {
+	fn from(e: GridError) -> Self {
+		match e {
+			GridError::Empty => ParseError::new(1, 1, "", "a grid row"),
+			GridError::BadCell { at, row, .. } => ParseError::new(at.y+1, at.x+1, &row, "a grid cell"),
+			GridError::Ragged { y, width, expected, row } => {
+				ParseError::new(y+1, width.min(expected)+1, &row, format!("a row of width {expected}"))
+			},
+			GridError::Uneven { width, .. } => ParseError::new(1, 1, "", format!("whole rows of width {width}")),
+		}
+	}
+}
+
+impl<T> Grid<T> {
+
+	/// Reads a grid with one byte per cell, and one line per row,
+	/// translating the bytes with `cell`
+	pub fn from_str_with(input:&str, mut cell:impl FnMut(u8) -> Option<T>) -> Result<Self,GridError> {
+
+		let width = input.lines().next().map_or(0, str::len);
+
+		if width == 0 {
+			return Err(GridError::Empty)
+		}
+
+		let mut items = Vec::with_capacity(input.len());
+
+		for (y,row) in input.lines().enumerate() {
+
+			if row.len() != width {
+				return Err(GridError::Ragged { y, width: row.len(), expected: width, row: row.to_string() })
+			}
+
+			for (x,&b) in row.as_bytes().iter().enumerate() {
+				let item = cell(b).ok_or_else(|| {
+					GridError::BadCell { at: (x,y).into(), found: b as char, row: row.to_string() }
+				})?;
+				items.push(item);
+			}
+		}
+
+		Ok(Self::new(width,items))
+	}
+}
+
+impl<T:TryFrom<u8>> std::str::FromStr for Grid<T> {
+	type Err = GridError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Self::from_str_with(s, |b| T::try_from(b).ok())
+	}
+}
+
+/// Renders one line per row, and one byte per cell
+impl<T:Copy+Into<u8>> Display for Grid<T> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		for row in self.rows() {
+			let bytes = row.iter().map(|&c| c.into()).collect_vec();
+			writeln!(f,"{}",String::from_utf8_lossy(&bytes))?;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	#[derive(Debug,Clone,Copy,PartialEq,Eq)]
+	struct Bit(bool);
+
+	impl TryFrom<u8> for Bit {
+		type Error = ();
+
+		fn try_from(value: u8) -> Result<Self, Self::Error> {
+			match value {
+				b'#' => Ok(Bit(true)),
+				b'.' => Ok(Bit(false)),
+				_ => Err(())
+			}
+		}
+	}
+
+	impl From<Bit> for u8 {
+		fn from(value: Bit) -> Self {
+			if value.0 { b'#' } else { b'.' }
+		}
+	}
+
+	#[test]
+	fn test_text() {
+
+		let grid:Grid<Bit> = "#..\n.#.\n".parse().unwrap();
+
+		assert_eq!((grid.size.width,grid.size.height),(3,2));
+		assert_eq!(grid[(1,1)], Bit(true));
+		assert_eq!(grid.to_string(), "#..\n.#.\n");
+
+		let actual = "#..\n.x.".parse::<Grid<Bit>>().unwrap_err();
+		assert_eq!(actual, GridError::BadCell { at: (1,1).into(), found: 'x', row: ".x.".into() });
+
+		let actual:ParseError = "#..\n.#".parse::<Grid<Bit>>().unwrap_err().into();
+		assert_eq!((actual.line,actual.column,actual.text.as_str()),(2,3,".#"));
+
+		assert_eq!("".parse::<Grid<Bit>>().err(), Some(GridError::Empty));
+	}
+}
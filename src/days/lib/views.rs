@@ -0,0 +1,178 @@
+//! Rows, columns and rectangular views over a [Grid], and its rearrangements
+
+use super::*;
+
+impl<T> Grid<T> {
+
+	pub fn row(&self, y:usize) -> &[T] {
+		let stride = self.stride();
+		&self.items[y*stride..(y+1)*stride]
+	}
+
+	pub fn row_mut(&mut self, y:usize) -> &mut [T] {
+		let stride = self.stride();
+		&mut self.items[y*stride..(y+1)*stride]
+	}
+
+	/// The rows, top to bottom
+	pub fn rows(&self) -> impl ExactSizeIterator<Item=&[T]> {
+		self.items.chunks_exact(self.stride())
+	}
+
+	/// The cells of column `x`, top to bottom
+	pub fn col(&self, x:usize) -> impl DoubleEndedIterator<Item=&T> + ExactSizeIterator {
+		assert!(x < self.size.width, "Column {x} is outside the grid");
+		self.items[x..].iter().step_by(self.stride())
+	}
+
+	/// The columns, left to right
+	pub fn cols(&self) -> impl DoubleEndedIterator<Item=impl DoubleEndedIterator<Item=&T> + ExactSizeIterator> + ExactSizeIterator {
+		(0..self.size.width).map(|x| self.col(x))
+	}
+
+	/// The rectangle of the given size, with its top left corner at `origin`
+	pub fn view(&self, origin:Location, size:Size) -> View<'_,T> {
+		assert!(
+			origin.x + size.width <= self.size.width && origin.y + size.height <= self.size.height,
+			"The view should be within the grid"
+		);
+		View { grid: self, origin, size }
+	}
+
+	/// Every view of the given size, row by row
+	pub fn windows(&self, size:Size) -> impl Iterator<Item=View<'_,T>> {
+		let xs = 0..(self.size.width + 1).saturating_sub(size.width);
+		let ys = 0..(self.size.height + 1).saturating_sub(size.height);
+		ys.cartesian_product(xs).map(move |(y,x)| self.view((x,y).into(), size))
+	}
+}
+
+impl<T:Clone> Grid<T> {
+
+	/// Swaps rows and columns
+	pub fn transpose(&self) -> Self {
+		let items = self.cols().flat_map(|col| col.cloned()).collect();
+		Self::new(self.size.height, items)
+	}
+
+	/// Rotates a quarter turn clockwise
+	pub fn rotate_right(&self) -> Self {
+		let items = self.cols().flat_map(|col| col.rev().cloned()).collect();
+		Self::new(self.size.height, items)
+	}
+
+	/// Rotates a quarter turn counterclockwise
+	pub fn rotate_left(&self) -> Self {
+		let items = self.cols().rev().flat_map(|col| col.cloned()).collect();
+		Self::new(self.size.height, items)
+	}
+
+	/// Rotates half a turn
+	pub fn rotate_half(&self) -> Self {
+		Self::new(self.size.width, self.items.iter().rev().cloned().collect())
+	}
+}
+
+/// A rectangle within a [Grid], indexed relative to its top left corner
+#[derive(Debug)]
+pub struct View<'a,T> {
+	grid: &'a Grid<T>,
+	pub origin: Location,
+	pub size: Size,
+}
+
+// Derived impls would require `T:Clone`
+impl<T> Clone for View<'_,T> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<T> Copy for View<'_,T> {}
+
+impl<T> HasSize for View<'_,T> {
+	fn size(&self) -> Size {
+		self.size
+	}
+}
+
+impl<'a,T> View<'a,T> {
+
+	pub fn row(&self, y:usize) -> &'a [T] {
+		assert!(y < self.size.height, "Row {y} is outside the view");
+		let start = self.origin.x;
+		&self.grid.row(self.origin.y + y)[start..start+self.size.width]
+	}
+
+	/// The rows, top to bottom
+	pub fn rows(&self) -> impl ExactSizeIterator<Item=&'a [T]> + use<'a,T> {
+		let view = *self;
+		(0..self.size.height).map(move |y| view.row(y))
+	}
+
+	/// The cells, row by row
+	pub fn iter(&self) -> impl Iterator<Item=&'a T> + use<'a,T> {
+		self.rows().flatten()
+	}
+}
+
+impl<T,I:Into<(usize,usize)>> Index<I> for View<'_,T> {
+	type Output = T;
+
+	fn index(&self, index: I) -> &Self::Output {
+		let (x,y) = index.into();
+		assert!(x < self.size.width && y < self.size.height, "({x},{y}) is outside the view");
+		&self.grid[(self.origin.x + x, self.origin.y + y)]
+	}
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	fn grid() -> Grid<u8> {
+		"abc\ndef\n".parse().unwrap()
+	}
+
+	fn text(rows:impl Iterator<Item=impl IntoIterator<Item=u8>>) -> Vec<String> {
+		rows.map(|r| String::from_utf8(r.into_iter().collect()).unwrap()).collect()
+	}
+
+	#[test]
+	fn test_rows_and_cols() {
+
+		let grid = grid();
+
+		assert_eq!(grid.row(1), b"def");
+		assert_eq!(text(grid.rows().map(|r| r.iter().copied())), ["abc","def"]);
+		assert_eq!(text(grid.cols().map(|c| c.copied())), ["ad","be","cf"]);
+	}
+
+	#[test]
+	fn test_views() {
+
+		let grid = grid();
+		let view = grid.view((1,0).into(), (2,2).into());
+
+		assert_eq!(view[(0,1)], b'e');
+		assert_eq!(text(view.rows().map(|r| r.iter().copied())), ["bc","ef"]);
+
+		let windows = grid.windows((2,2).into()).map(|w| w.iter().copied().collect_vec()).collect_vec();
+		assert_eq!(windows, [b"abde".to_vec(), b"bcef".to_vec()]);
+
+		assert_eq!(grid.windows((4,1).into()).count(), 0);
+	}
+
+	#[test]
+	fn test_rearrangements() {
+
+		let grid = grid();
+
+		assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+		assert_eq!(grid.rotate_right().to_string(), "da\neb\nfc\n");
+		assert_eq!(grid.rotate_left().to_string(), "cf\nbe\nad\n");
+		assert_eq!(grid.rotate_half().to_string(), "fed\ncba\n");
+		assert_eq!(grid.rotate_right().rotate_left(), grid);
+	}
+}
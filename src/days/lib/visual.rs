@@ -0,0 +1,249 @@
+//! Pictures of a [Grid], with a colour per cell:
+//! PPM and PNG images, SVG drawings, and ANSI terminal frames
+
+use std::{io::Write, path::Path, time::Duration};
+
+use super::*;
+
+#[derive(Debug,Clone,Copy,PartialEq,Eq,Hash,Default)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+
+	pub const BLACK:Self = Self::from_hex(0x000000);
+	pub const WHITE:Self = Self::from_hex(0xffffff);
+
+	/// From `0xRRGGBB`
+	pub const fn from_hex(hex:u32) -> Self {
+		Self((hex >> 16) as u8, (hex >> 8) as u8, hex as u8)
+	}
+}
+
+impl Display for Rgb {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f,"#{:02x}{:02x}{:02x}",self.0,self.1,self.2)
+	}
+}
+
+impl<T> Grid<T> {
+
+	/// The pixels row by row, `scale` pixels wide and high per cell
+	fn pixels(&self, colour:impl Fn(&T) -> Rgb, scale:usize) -> impl Iterator<Item=Vec<Rgb>> {
+		self.rows().flat_map(move |row| {
+			let line = row.iter()
+				.flat_map(|item| std::iter::repeat_n(colour(item), scale))
+				.collect_vec();
+			std::iter::repeat_n(line, scale)
+		})
+	}
+
+	/// A binary PPM image
+	pub fn to_ppm(&self, colour:impl Fn(&T) -> Rgb, scale:usize) -> Vec<u8> {
+
+		let (width, height) = (self.size.width * scale, self.size.height * scale);
+		let mut ppm = format!("P6\n{width} {height}\n255\n").into_bytes();
+
+		for Rgb(r,g,b) in self.pixels(colour, scale).flatten() {
+			ppm.extend([r,g,b]);
+		}
+
+		ppm
+	}
+
+	/// An uncompressed PNG image
+	pub fn to_png(&self, colour:impl Fn(&T) -> Rgb, scale:usize) -> Vec<u8> {
+
+		let (width, height) = (self.size.width * scale, self.size.height * scale);
+
+		// Each scanline starts with its filter type, none
+		let scanlines = self.pixels(colour, scale)
+			.flat_map(|line| std::iter::once(0).chain(line.into_iter().flat_map(|Rgb(r,g,b)| [r,g,b])))
+			.collect_vec();
+
+		let mut header = vec![];
+		header.extend((width as u32).to_be_bytes());
+		header.extend((height as u32).to_be_bytes());
+		// 8 bit RGB, default compression and filtering, no interlacing
+		header.extend([8,2,0,0,0]);
+
+		let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+		png_chunk(&mut png, b"IHDR", &header);
+		png_chunk(&mut png, b"IDAT", &zlib_stored(&scanlines));
+		png_chunk(&mut png, b"IEND", &[]);
+		png
+	}
+
+	/// An SVG drawing, with `scale` units per cell
+	pub fn to_svg(&self, colour:impl Fn(&T) -> Rgb, scale:usize) -> String {
+
+		let Size { width, height } = self.size;
+
+		let mut svg = format!(
+			"<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {width} {height}\" shape-rendering=\"crispEdges\">\n",
+			width * scale, height * scale
+		);
+
+		for (y,row) in self.rows().enumerate() {
+			// Runs of a colour are drawn as a single rectangle
+			let mut x = 0;
+			for (fill,run) in row.iter().map(&colour).chunk_by(|&c| c).into_iter() {
+				let len = run.count();
+				svg.push_str(&format!("<rect x=\"{x}\" y=\"{y}\" width=\"{len}\" height=\"1\" fill=\"{fill}\"/>\n"));
+				x += len;
+			}
+		}
+
+		svg.push_str("</svg>\n");
+		svg
+	}
+
+	/// Coloured blocks for a terminal, two characters wide per cell
+	pub fn to_ansi(&self, colour:impl Fn(&T) -> Rgb) -> String {
+		self.rows()
+			.map(|row| {
+				let cells = row.iter()
+					.map(|item| {
+						let Rgb(r,g,b) = colour(item);
+						format!("\x1b[48;2;{r};{g};{b}m  ")
+					})
+					.join("");
+				format!("{cells}\x1b[0m\n")
+			})
+			.join("")
+	}
+}
+
+fn png_chunk(png:&mut Vec<u8>, kind:&[u8;4], data:&[u8]) {
+	png.extend((data.len() as u32).to_be_bytes());
+	png.extend(kind);
+	png.extend(data);
+	png.extend(crc32(kind.iter().chain(data)).to_be_bytes());
+}
+
+fn crc32<'a>(bytes:impl IntoIterator<Item=&'a u8>) -> u32 {
+	!bytes.into_iter().fold(!0u32, |crc,&b| {
+		(0..8).fold(crc ^ b as u32, |c,_| if c & 1 == 1 { (c >> 1) ^ 0xedb88320 } else { c >> 1 })
+	})
+}
+
+/// A zlib stream with the data in stored, uncompressed, deflate blocks
+fn zlib_stored(data:&[u8]) -> Vec<u8> {
+
+	let mut zlib = vec![0x78,0x01];
+
+	let blocks = data.chunks(u16::MAX as usize).collect_vec();
+
+	for (i,block) in blocks.iter().enumerate() {
+		let last = i + 1 == blocks.len();
+		let len = block.len() as u16;
+		zlib.push(last as u8);
+		zlib.extend(len.to_le_bytes());
+		zlib.extend((!len).to_le_bytes());
+		zlib.extend(*block);
+	}
+
+	if blocks.is_empty() {
+		zlib.extend([1,0,0,0xff,0xff]);
+	}
+
+	let (a,b) = data.iter().fold((1u32,0u32), |(a,b),&d| {
+		let a = (a + d as u32) % 65521;
+		(a, (b + a) % 65521)
+	});
+
+	zlib.extend(((b << 16) | a).to_be_bytes());
+	zlib
+}
+
+/// Successive states of a grid, as frames
+#[derive(Debug,Clone,Default)]
+pub struct Animation {
+	frames: Vec<Grid<Rgb>>
+}
+
+impl Animation {
+
+	pub fn record<T>(&mut self, grid:&Grid<T>, colour:impl Fn(&T) -> Rgb) {
+		let pixels = grid.iter().map(colour).collect();
+		self.frames.push(Grid::new(grid.stride(), pixels));
+	}
+
+	pub fn frames(&self) -> &[Grid<Rgb>] {
+		&self.frames
+	}
+
+	/// Draws the frames in a terminal, each over the previous one
+	pub fn play(&self, out:&mut impl Write, delay:Duration) -> std::io::Result<()> {
+		for frame in self.frames.iter() {
+			write!(out, "\x1b[H\x1b[2J{}", frame.to_ansi(|&c| c))?;
+			out.flush()?;
+			std::thread::sleep(delay);
+		}
+		Ok(())
+	}
+
+	/// Saves the frames as `frame_0000.png`, `frame_0001.png`... in `dir`
+	pub fn save_png_frames(&self, dir:&Path, scale:usize) -> Result<(),AppError> {
+		std::fs::create_dir_all(dir).map_err(AppError::io(dir))?;
+		for (i,frame) in self.frames.iter().enumerate() {
+			let path = dir.join(format!("frame_{i:04}.png"));
+			std::fs::write(&path, frame.to_png(|&c| c, scale)).map_err(AppError::io(path))?;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	fn colour(&b:&u8) -> Rgb {
+		if b == b'#' { Rgb::BLACK } else { Rgb::WHITE }
+	}
+
+	#[test]
+	fn test_images() {
+
+		let grid:Grid<u8> = "#.\n..\n".parse().unwrap();
+
+		let ppm = grid.to_ppm(colour, 2);
+		assert!(ppm.starts_with(b"P6\n4 4\n255\n"));
+		assert_eq!(ppm.len(), 11 + 4 * 4 * 3);
+		assert_eq!(&ppm[11..17], [0,0,0,0,0,0]);
+
+		let png = grid.to_png(colour, 1);
+		assert!(png.starts_with(b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR"));
+		assert!(png.ends_with(b"IEND\xae\x42\x60\x82"));
+
+		let svg = grid.to_svg(colour, 10);
+		assert!(svg.contains("width=\"20\""));
+		assert!(svg.contains("<rect x=\"0\" y=\"1\" width=\"2\" height=\"1\" fill=\"#ffffff\"/>"));
+
+		assert_eq!(grid.to_ansi(colour).lines().count(), 2);
+	}
+
+	#[test]
+	fn test_checksums() {
+		assert_eq!(crc32(b"123456789"), 0xcbf43926);
+		assert_eq!(zlib_stored(b"Wikipedia").split_off(16), 0x11e60398u32.to_be_bytes());
+	}
+
+	#[test]
+	fn test_animation() {
+
+		let mut animation = Animation::default();
+		let mut grid:Grid<u8> = "#.\n".parse().unwrap();
+
+		animation.record(&grid, colour);
+		grid[(1,0)] = b'#';
+		animation.record(&grid, colour);
+
+		assert_eq!(animation.frames().len(), 2);
+		assert_eq!(animation.frames()[1][(1,0)], Rgb::BLACK);
+
+		let mut out = vec![];
+		animation.play(&mut out, Duration::ZERO).unwrap();
+		assert_eq!(String::from_utf8(out).unwrap().matches("\x1b[H").count(), 2);
+	}
+}
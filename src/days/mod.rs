@@ -3,67 +3,227 @@
 mod lib;
 use lib::*;
 
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day06;
-mod day07;
-mod day08;
-mod day10;
+/// Declares the day modules, and collects their registered solutions into [REGISTRY]
+macro_rules! days {
+	($($day:ident),* $(,)?) => {
+		$( mod $day; )*
+
+		/// Every registered solution, grouped by day
+		pub static REGISTRY:&[&[Entry]] = &[ $( $day::SOLUTIONS ),* ];
+	};
+}
+
+days! {
+	day01,
+	day02,
+	day03,
+	day04,
+	day05,
+	day06,
+	day07,
+	day08,
+	day09,
+	day10,
+}
 
 use super::*;
 
-use std::fmt::Display;
+use std::{fmt::Display, path::Path};
 use itertools::{Itertools,assert_equal};
 
 use indoc::indoc;
 use aoc_driver::Part;
-use peg::{error::ParseError, str::LineCol};
+use peg::{error::ParseError as PegError, str::LineCol};
+
+/// Parameters of a puzzle, that examples or variants may change.
+/// The default values are those of the real puzzle.
+pub trait Params: Default {
+
+	/// Sets a parameter by name
+	fn set(&mut self, key:&str, value:&str) -> Result<(),String>;
+
+	/// Parses a comma separated list of `key=value` assignments,
+	/// over the default values
+	fn parse(spec:&str) -> Result<Self,AppError> {
+
+		let mut params = Self::default();
+
+		for assignment in spec.split(',').map(str::trim).filter(|a| !a.is_empty()) {
+
+			let (key,value) = assignment.split_once('=')
+				.ok_or_else(|| AppError::BadConfiguration(format!("Expected `key=value`, found `{assignment}`")))?;
+
+			params.set(key.trim(), value.trim()).map_err(AppError::BadConfiguration)?;
+		}
+
+		Ok(params)
+	}
+}
+
+impl Params for () {
+	fn set(&mut self, key:&str, _:&str) -> Result<(),String> {
+		Err(format!("Unknown parameter `{key}`, this puzzle has none"))
+	}
+}
+
+/// Parses a parameter value
+fn param<T:std::str::FromStr>(key:&str, value:&str) -> Result<T,String> {
+	value.parse().map_err(|_| format!("Invalid value `{value}` for parameter `{key}`"))
+}
 
 trait Solution {
 
 	const DAY: i32;
 	const PART: Part;
 
-	// NOTICE: Using impl Diplay causes lifetie issues when calling
-	// `aoc_driver::calculate_and_post`
-	fn solve(input:&str) -> impl Display;
+	type Params: Params;
+
+	fn solve_with(input:&str, params:&Self::Params) -> Result<impl Display,ParseError>;
 
+	/// Explains how the answer is reached, e.g. listing the choices made.
+	/// Most solutions have no explanation
+	fn explain_with(_input:&str, _params:&Self::Params) -> Result<Option<String>,ParseError> {
+		Ok(None)
+	}
+
+	/// Records the successive states of the puzzle while solving.
+	/// Most solutions record nothing
+	fn record_with(_input:&str, _params:&Self::Params) -> Result<Option<Animation>,ParseError> {
+		Ok(None)
+	}
+
+	/// Solves the real puzzle
+	fn solve(input:&str) -> Result<impl Display,ParseError> {
+		// NOTICE: The answer may borrow from the parameters, which are temporary
+		Self::solve_with(input, &Default::default()).map(|answer| answer.to_string())
+	}
+
+	/// Solves, reporting malformed input as an error
+	fn try_solve_with(input:&str, params:&Self::Params) -> Result<String,AppError> {
+		Self::solve_with(input, params)
+			.map(|answer| answer.to_string())
+			.map_err(|e| e.on_day(Self::DAY).into())
+	}
+
+	/// Solves the real puzzle, reporting malformed input as an error
+	fn try_solve(input:&str) -> Result<String,AppError> {
+		Self::solve(input)
+			.map(|answer| answer.to_string())
+			.map_err(|e| e.on_day(Self::DAY).into())
+	}
+
+	// Only reached from the submission tests
+	#[cfg_attr(not(all(test,feature="submit")), allow(dead_code))]
 	fn try_submit() -> Result<(),AppError> {
 
+		use crate::store::{FsInputStore, InputStore};
+
 		let cookie: String = cookie()?;
 
-		let solve = |input: &str| Self::solve(input).to_string();
-
-		aoc_driver::calculate_and_post(
-			&cookie, YEAR, Self::DAY, Self::PART,
-			Some(format!("inputs/{}.txt",Self::DAY)),
-			Some(format!("cache/{}.json",Self::DAY)),
-			// NOTICE: Using Solution::solve directly causes lifetimes issue
-			solve
-		).map_err(|e| {
-			let msg = format!("Solution for day {} rejected: {e:?}",Self::DAY);
-			AppError::IncorrectSolution(msg)
-		})
+		let store = FsInputStore::from_env();
+		let part = Self::PART.into();
+
+		let input = store.get_or_fetch(Self::DAY, &cookie)?;
+
+		let answer = Self::try_solve(&input)?;
+
+		crate::ledger::submit(&store, &cookie, Self::DAY, part, &answer)
 	}
 }
 
 /// A generic parse for multiline input
-/// Takes a Rust-peg parse function that is applied to every line
-fn parse<'a,T>(input: &'a str, parse:fn(&'a str) -> Result<T,ParseError<LineCol>>) -> impl Iterator<Item=T> + use<'a,T> {
-	input.lines().map(move |l| {
-		parse(l)
-			.inspect_err(|e| eprintln!("Failed parsing {l}: {e}"))
-			.expect("Parser should not fail")
+/// Takes a Rust-peg parse function that is applied to every line,
+/// failing on the first line that does not parse
+fn parse<'a,T>(input: &'a str, parse:fn(&'a str) -> Result<T,PegError<LineCol>>) -> Result<impl Iterator<Item=T> + use<'a,T>,ParseError> {
+	input.lines()
+		.enumerate()
+		.map(|(n,l)| parse_line(n, l, parse))
+		.collect::<Result<Vec<T>,_>>()
+		.map(Vec::into_iter)
+}
+
+/// Applies a Rust-peg parse function to the line at index `n`
+fn parse_line<'a,T>(n:usize, line:&'a str, parse:fn(&'a str) -> Result<T,PegError<LineCol>>) -> Result<T,ParseError> {
+	parse(line).map_err(|e| {
+		let LineCol { line:l, column, .. } = e.location;
+		ParseError::new(n+l, column, line, e.expected.to_string())
 	})
 }
 
+/// A registered solution, with the puzzle it solves
+#[derive(Clone,Copy)]
+pub struct Entry {
+	pub day: i32,
+	pub part: i32,
+	pub solve: fn(&str) -> Result<String,AppError>,
+	/// Solves with the parameters given as `key=value` assignments, see [Params::parse]
+	pub solve_with: fn(&str, &str) -> Result<String,AppError>,
+	/// Explains the answer, with the parameters given as for `solve_with`
+	pub explain: fn(&str, &str) -> Result<Option<String>,AppError>,
+	/// Saves the recorded frames in the given directory, as for [Animation::save_png_frames].
+	/// Returns whether the solution records any
+	pub record: fn(&str, &str, &Path) -> Result<bool,AppError>,
+}
+
+impl Entry {
+
+	const fn of<S:Solution>() -> Self {
+
+		let part = match S::PART {
+			Part::Part1 => 1,
+			Part::Part2 => 2,
+			Part::Part(n) => n,
+		};
+
+		Self { day: S::DAY, part, solve: S::try_solve, solve_with: solve_with_spec::<S>, explain: explain_with_spec::<S>, record: record_with_spec::<S> }
+	}
+}
+
+fn solve_with_spec<S:Solution>(input:&str, spec:&str) -> Result<String,AppError> {
+	S::try_solve_with(input, &S::Params::parse(spec)?)
+}
+
+fn explain_with_spec<S:Solution>(input:&str, spec:&str) -> Result<Option<String>,AppError> {
+	S::explain_with(input, &S::Params::parse(spec)?).map_err(|e| e.on_day(S::DAY).into())
+}
+
+/// How many pixels wide and high a cell is in the saved frames
+const FRAME_SCALE:usize = 4;
+
+fn record_with_spec<S:Solution>(input:&str, spec:&str, dir:&Path) -> Result<bool,AppError> {
+	match S::record_with(input, &S::Params::parse(spec)?).map_err(|e| e.on_day(S::DAY))? {
+		Some(animation) => animation.save_png_frames(dir, FRAME_SCALE).map(|_| true),
+		None => Ok(false),
+	}
+}
+
+/// Iterates every registered solution, ordered by day and part
+pub fn solutions() -> impl Iterator<Item=&'static Entry> {
+	REGISTRY.iter().copied().flatten()
+}
+
+/// Looks up the solution for the given day and part
+pub fn find(day:i32, part:i32) -> Option<&'static Entry> {
+	solutions().find(|e| e.day == day && e.part == part)
+}
+
+/// Registers the solutions of a day module
+macro_rules! register {
+	($($part:ty),+ $(,)?) => {
+		pub(super) const SOLUTIONS:&[$crate::days::Entry] = &[
+			$( $crate::days::Entry::of::<$part>() ),+
+		];
+	};
+}
+
+use register;
+
+#[cfg(test)]
 macro_rules! submit {
 	($part:ty) => {
 		::paste::paste! {
-			#[cfg_attr(feature="submit", test)]
+			#[cfg(feature="submit")]
+			#[test]
 			fn [<test_ $part:lower _submit>]()-> Result<(), $crate::AppError> {
 				<$part as $crate::days::Solution>::try_submit()
 			}
@@ -71,4 +231,113 @@ macro_rules! submit {
 	};
 }
 
+#[cfg(test)]
 use submit;
+
+/// Generates a test for each example, checking the solution's answer:
+///
+/// ```ignore
+/// examples! {
+///     part1: Part1 => EXAMPLE_INPUT => "3",
+///     part1_other: Part1 => OTHER_INPUT => "5",
+/// }
+/// ```
+///
+/// Examples with different puzzle parameters can give them,
+/// e.g. `part1: Part1(Connections { limit: 10 }) => ...`
+#[cfg(test)]
+macro_rules! examples {
+	($( $name:ident : $part:ident $(($params:expr))? => $input:expr => $expected:expr ),+ $(,)?) => {
+		::paste::paste! {
+			$(
+				#[test]
+				fn [<test_ $name _example>]() -> Result<(), $crate::AppError> {
+					#[allow(unused_variables)]
+					let params = <$part as $crate::days::Solution>::Params::default();
+					$( let params = $params; )?
+					let actual = <$part as $crate::days::Solution>::try_solve_with($input, &params)?;
+					::pretty_assertions::assert_eq!(actual, $expected);
+					Ok(())
+				}
+			)+
+		}
+	};
+}
+
+#[cfg(test)]
+use examples;
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	#[test]
+	fn test_registry() {
+
+		let actual = solutions().map(|e| (e.day,e.part)).collect_vec();
+		let expected = actual.iter().copied().sorted().dedup().collect_vec();
+
+		// Registered once, in order
+		assert_eq!(actual,expected);
+
+		assert!(find(1,1).is_some());
+		assert!(find(1,3).is_none());
+	}
+
+	#[test]
+	fn test_params() {
+
+		assert!(<() as Params>::parse("").is_ok());
+		assert!(<() as Params>::parse("limit=10").is_err());
+
+		let entry = find(8,1).unwrap();
+
+		assert!((entry.solve_with)("1,2,3", "limit").is_err());
+		assert!((entry.solve_with)("1,2,3", "limit=ten").is_err());
+	}
+
+	#[test]
+	fn test_explain() {
+
+		let machine = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}";
+
+		assert!((find(10,1).unwrap().explain)(machine, "").unwrap().is_some());
+		assert!((find(1,1).unwrap().explain)("L68", "").unwrap().is_none());
+	}
+
+	// Checks every solution against the known answers in `answers/`
+	#[cfg(feature="verify")]
+	#[test]
+	fn test_verify() -> Result<(),AppError> {
+
+		use crate::answers::{verify, Verdict};
+		use crate::store::FsInputStore;
+
+		let store = FsInputStore::from_env();
+
+		let mut expected = vec![];
+		let mut actual = vec![];
+
+		for entry in solutions() {
+
+			let label = format!("day {} part {}", entry.day, entry.part);
+
+			match verify(&store, entry)? {
+				Verdict::Correct(answer) => {
+					expected.push(format!("{label}: {answer}"));
+					actual.push(format!("{label}: {answer}"));
+				},
+				Verdict::Mismatch { expected: e, actual: a } => {
+					expected.push(format!("{label}: {e}"));
+					actual.push(format!("{label}: {a}"));
+				},
+				Verdict::Skipped => {}
+			}
+		}
+
+		::pretty_assertions::assert_eq!(expected,actual);
+
+		Ok(())
+	}
+}
@@ -0,0 +1,103 @@
+use std::{env::VarError, error::Error, fmt::Display, path::PathBuf};
+
+#[derive(Debug)]
+pub enum AppError {
+	/// The `COOKIE` environment variable is not available
+	MissingCookie(VarError),
+	/// Invalid command line arguments, or project files
+	BadConfiguration(String),
+	/// A file could not be read or written
+	Io { path: PathBuf, source: std::io::Error },
+	/// The puzzle input is malformed
+	Parse(ParseError),
+	/// The server did not accept the answer, now or on an earlier submission
+	Rejected { day: i32, part: i32, answer: String, outcome: String },
+	/// Some solutions disagree with their known answers
+	Mismatch { count: usize },
+	/// The server asks to wait before submitting again
+	RateLimited { wait: String },
+	/// The server could not be reached
+	Network(Box<dyn Error + Send>),
+}
+
+impl AppError {
+
+	pub fn io(path:impl Into<PathBuf>) -> impl FnOnce(std::io::Error) -> Self {
+		|source| AppError::Io { path: path.into(), source }
+	}
+}
+
+impl Display for AppError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			AppError::MissingCookie(_) => f.write_str("Cookie error! Set COOKIE to the session cookie"),
+			AppError::BadConfiguration(msg) => f.write_str(msg),
+			AppError::Io { path, .. } => write!(f,"Cannot access {}",path.display()),
+			AppError::Parse(_) => f.write_str("Malformed puzzle input"),
+			AppError::Rejected { day, part, answer, outcome } => write!(f,"Day {day} part {part} rejected {answer} ({outcome})"),
+			AppError::Mismatch { count } => write!(f,"{count} solution(s) disagree with the known answers"),
+			AppError::RateLimited { wait } => write!(f,"Submitting too often, wait {wait}"),
+			AppError::Network(_) => f.write_str("Cannot reach the server"),
+		}
+	}
+}
+
+impl Error for AppError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			AppError::MissingCookie(e) => Some(e),
+			AppError::Io { source, .. } => Some(source),
+			AppError::Parse(e) => Some(e),
+			AppError::Network(e) => Some(e.as_ref()),
+			AppError::BadConfiguration(_)
+			| AppError::Rejected { .. }
+			| AppError::Mismatch { .. }
+			| AppError::RateLimited { .. } => None,
+		}
+	}
+}
+
+/// A malformed puzzle input, located at the offending line
+#[derive(Debug,Clone,PartialEq,Eq)]
+pub struct ParseError {
+	/// The puzzle day, once known
+	pub day: Option<i32>,
+	/// 1-based line number
+	pub line: usize,
+	/// 1-based column number
+	pub column: usize,
+	/// The offending line
+	pub text: String,
+	/// What the parser was expecting at that column
+	pub expected: String,
+}
+
+impl ParseError {
+
+	pub fn new(line:usize, column:usize, text:&str, expected:impl Into<String>) -> Self {
+		Self { day: None, line, column, text: text.to_string(), expected: expected.into() }
+	}
+
+	pub fn on_day(self, day:i32) -> Self {
+		Self { day: Some(day), ..self }
+	}
+}
+
+impl Display for ParseError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		if let Some(day) = self.day {
+			write!(f,"day {day}, ")?;
+		}
+		writeln!(f,"line {}, column {}: expected {}",self.line,self.column,self.expected)?;
+		writeln!(f,"{}",self.text)?;
+		write!(f,"{:>1$}","^",self.column)
+	}
+}
+
+impl Error for ParseError {}
+
+impl From<ParseError> for AppError {
+	fn from(e: ParseError) -> Self {
+		AppError::Parse(e)
+	}
+}
@@ -0,0 +1,383 @@
+//! Every answer submitted for a day, with the verdict of the server,
+//! kept in `ledger/<DAY>.json` next to the inputs.
+//!
+//! The ledger avoids contacting the server when the verdict is already known,
+//! and narrows numeric answers down with the "too high" and "too low" hints.
+
+use std::fmt::Display;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{AppError, YEAR, store::FsInputStore};
+
+/// The verdict of the server on a submitted answer
+#[derive(Debug,Clone,PartialEq,Eq,Serialize,Deserialize)]
+pub enum Outcome {
+	Correct,
+	TooHigh,
+	TooLow,
+	/// Wrong, without any hint
+	Incorrect,
+	/// Submitted too soon after the previous answer, and not checked
+	Wait(String),
+	/// Submitted for a part already solved, or still locked, and not checked
+	WrongLevel,
+}
+
+impl Outcome {
+
+	/// Recognises the verdict in the page returned by the server
+	pub fn from_response(body:&str) -> Option<Self> {
+
+		const WAIT:&str = "You gave an answer too recently";
+
+		// Sent whether the answer is right or not, so it says nothing about it
+		if body.contains("You don't seem to be solving the right level") {
+			Some(Outcome::WrongLevel)
+		} else if body.contains("That's the right answer!") {
+			Some(Outcome::Correct)
+		} else if body.contains("your answer is too high") {
+			Some(Outcome::TooHigh)
+		} else if body.contains("your answer is too low") {
+			Some(Outcome::TooLow)
+		} else if body.contains("That's not the right answer") {
+			Some(Outcome::Incorrect)
+		} else if body.contains(WAIT) {
+			let wait = body.split_once("You have ")
+				.and_then(|(_,rest)| rest.split_once(" left to wait"))
+				.map_or("a moment", |(wait,_)| wait);
+			Some(Outcome::Wait(wait.to_string()))
+		} else {
+			None
+		}
+	}
+}
+
+impl Display for Outcome {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Outcome::Correct => f.write_str("correct"),
+			Outcome::TooHigh => f.write_str("too high"),
+			Outcome::TooLow => f.write_str("too low"),
+			Outcome::Incorrect => f.write_str("incorrect"),
+			Outcome::Wait(wait) => write!(f,"wait {wait}"),
+			Outcome::WrongLevel => f.write_str("wrong level"),
+		}
+	}
+}
+
+/// A submitted answer
+#[derive(Debug,Clone,PartialEq,Eq,Serialize,Deserialize)]
+pub struct Guess {
+	pub part: i32,
+	pub answer: String,
+	pub time: DateTime<Utc>,
+	pub outcome: Outcome,
+}
+
+impl Guess {
+	fn rejected(&self, day:i32) -> AppError {
+		AppError::Rejected { day, part: self.part, answer: self.answer.clone(), outcome: self.outcome.to_string() }
+	}
+}
+
+impl Display for Guess {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f,"{} ({}, submitted {})",self.answer,self.outcome,self.time.format("%Y-%m-%d %H:%M:%S UTC"))
+	}
+}
+
+/// The submissions of a day, oldest first
+#[derive(Debug,Default,Clone,PartialEq,Eq,Serialize,Deserialize)]
+pub struct Ledger {
+	guesses: Vec<Guess>,
+}
+
+/// The submission cache written by `aoc_driver`, used to seed a new ledger
+#[derive(Deserialize)]
+struct DriverCache {
+	parts: std::collections::HashMap<i32,std::collections::HashMap<String,DriverResponse>>,
+}
+
+#[derive(Deserialize)]
+struct DriverResponse {
+	submission_time: DateTime<Utc>,
+	response: Result<(),DriverError>,
+}
+
+#[derive(Deserialize)]
+enum DriverError {
+	Incorrect,
+	RateLimit(String),
+	#[serde(other)]
+	Other,
+}
+
+impl Ledger {
+
+	/// Loads the ledger of a day, seeding it from the `aoc_driver` cache the first time
+	pub fn load(store:&FsInputStore, day:i32) -> Result<Self,AppError> {
+
+		let path = store.ledger_path(day);
+
+		if path.exists() {
+			let content = std::fs::read_to_string(&path).map_err(AppError::io(&path))?;
+			return serde_json::from_str(&content)
+				.map_err(|e| AppError::BadConfiguration(format!("Malformed {}, {e}", path.display())))
+		}
+
+		let path = store.cache_path(day);
+
+		if path.exists() {
+			let content = std::fs::read_to_string(&path).map_err(AppError::io(&path))?;
+			// The cache is only a hint, ignore it when unreadable
+			if let Ok(cache) = serde_json::from_str(&content) {
+				return Ok(Self::from_driver_cache(cache))
+			}
+		}
+
+		Ok(Self::default())
+	}
+
+	fn from_driver_cache(cache:DriverCache) -> Self {
+
+		let mut guesses:Vec<Guess> = cache.parts.into_iter()
+			.flat_map(|(part,responses)| responses.into_iter().map(move |(answer,r)| (part,answer,r)))
+			.filter_map(|(part,answer,r)| {
+				let outcome = match r.response {
+					Ok(()) => Outcome::Correct,
+					Err(DriverError::Incorrect) => Outcome::Incorrect,
+					Err(DriverError::RateLimit(wait)) => Outcome::Wait(wait),
+					Err(DriverError::Other) => return None,
+				};
+				Some(Guess { part, answer, time: r.submission_time, outcome })
+			})
+			.collect();
+
+		guesses.sort_by_key(|g| g.time);
+
+		Self { guesses }
+	}
+
+	pub fn save(&self, store:&FsInputStore, day:i32) -> Result<(),AppError> {
+		store.init()?;
+		let path = store.ledger_path(day);
+		let content = serde_json::to_string_pretty(self).expect("The ledger should serialize");
+		std::fs::write(&path, content).map_err(AppError::io(path))
+	}
+
+	/// The submissions of a part, oldest first
+	pub fn guesses(&self, part:i32) -> impl Iterator<Item=&Guess> {
+		self.guesses.iter().filter(move |g| g.part == part)
+	}
+
+	/// The latest verdict on an answer, ignoring the unchecked ones
+	pub fn lookup(&self, part:i32, answer:&str) -> Option<&Guess> {
+		self.guesses(part)
+			.filter(|g| g.answer == answer && !matches!(g.outcome, Outcome::Wait(_)))
+			.last()
+	}
+
+	/// The greatest answer known to be too low, and the least known to be too high
+	pub fn bounds(&self, part:i32) -> (Option<i64>,Option<i64>) {
+
+		let numbers = |outcome:Outcome| self.guesses(part)
+			.filter(move |g| g.outcome == outcome)
+			.filter_map(|g| g.answer.trim().parse::<i64>().ok());
+
+		(numbers(Outcome::TooLow).max(), numbers(Outcome::TooHigh).min())
+	}
+
+	/// How long the server still asks to wait, when the latest submission was too soon
+	pub fn waiting(&self, now:DateTime<Utc>) -> Option<Duration> {
+
+		let latest = self.guesses.last()?;
+
+		let Outcome::Wait(wait) = &latest.outcome else {
+			return None
+		};
+
+		let remaining = latest.time + parse_wait(wait)? - now;
+
+		(remaining > Duration::zero()).then_some(remaining)
+	}
+
+	/// Checks an answer against the ledger, and only then submits it with `post`,
+	/// recording the verdict.
+	/// Warns on stderr when a numeric answer is outside the known bounds.
+	pub fn submit(
+		&mut self,
+		day:i32,
+		part:i32,
+		answer:&str,
+		post:impl FnOnce(&str) -> Result<Outcome,AppError>
+	) -> Result<(),AppError> {
+
+		if let Some(remaining) = self.waiting(Utc::now()) {
+			return Err(AppError::RateLimited { wait: format!("{}s", remaining.num_seconds() + 1) })
+		}
+
+		if let Some(solved) = self.guesses(part).find(|g| g.outcome == Outcome::Correct) {
+			return if solved.answer == answer {
+				Ok(())
+			} else {
+				Err(AppError::BadConfiguration(format!("Day {day} part {part} was already solved with {solved}")))
+			}
+		}
+
+		if let Some(guess) = self.lookup(part, answer) {
+			return Err(guess.rejected(day))
+		}
+
+		if let Ok(n) = answer.trim().parse::<i64>() {
+			match self.bounds(part) {
+				(Some(low),_) if n <= low => eprintln!("Warning: {n} is not above {low}, known to be too low"),
+				(_,Some(high)) if n >= high => eprintln!("Warning: {n} is not below {high}, known to be too high"),
+				_ => (),
+			}
+		}
+
+		let outcome = post(answer)?;
+
+		// Not a verdict on the answer, that could be submitted again once the level is right
+		if outcome == Outcome::WrongLevel {
+			return Err(AppError::BadConfiguration(format!(
+				"Day {day} part {part} is not the level being solved, it is already solved or still locked"
+			)))
+		}
+
+		let guess = Guess { part, answer: answer.to_string(), time: Utc::now(), outcome };
+		self.guesses.push(guess.clone());
+
+		match guess.outcome {
+			Outcome::Correct => Ok(()),
+			Outcome::Wait(wait) => Err(AppError::RateLimited { wait }),
+			_ => Err(guess.rejected(day)),
+		}
+	}
+}
+
+/// Reads waits such as `42s` or `4m 12s`
+fn parse_wait(wait:&str) -> Option<Duration> {
+	wait.split_whitespace()
+		.map(|w| {
+			let (n,unit) = w.split_at(w.find(|c:char| !c.is_ascii_digit())?);
+			let n = n.parse().ok()?;
+			match unit {
+				"h" => Some(Duration::hours(n)),
+				"m" => Some(Duration::minutes(n)),
+				"s" => Some(Duration::seconds(n)),
+				_ => None,
+			}
+		})
+		.sum()
+}
+
+/// Posts an answer to the server, and reads its verdict
+fn post(cookie:&str, day:i32, part:i32, answer:&str) -> Result<Outcome,AppError> {
+
+	let network = |e:ureq::Error| AppError::Network(Box::new(e));
+
+	let body = ureq::post(&format!("https://adventofcode.com/{YEAR}/day/{day}/answer"))
+		.set("User-Agent", concat!("aoc_2025/", env!("CARGO_PKG_VERSION")))
+		.set("Cookie", &format!("session={cookie}"))
+		.send_form(&[("level", &part.to_string()), ("answer", answer)])
+		.map_err(network)?
+		.into_string()
+		.map_err(|e| network(e.into()))?;
+
+	Outcome::from_response(&body).ok_or_else(|| {
+		AppError::BadConfiguration(format!("Unexpected response for day {day} part {part}, is it locked?"))
+	})
+}
+
+/// Submits an answer, unless the ledger of the day already knows its verdict
+pub fn submit(store:&FsInputStore, cookie:&str, day:i32, part:i32, answer:&str) -> Result<(),AppError> {
+
+	let mut ledger = Ledger::load(store, day)?;
+
+	let result = ledger.submit(day, part, answer, |answer| post(cookie, day, part, answer));
+
+	ledger.save(store, day)?;
+
+	result
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	#[test]
+	fn test_from_response() {
+
+		let body = "<p>That's not the right answer; your answer is too high.  If you're stuck...</p>";
+		assert_eq!(Outcome::from_response(body), Some(Outcome::TooHigh));
+
+		let body = "<p>That's not the right answer.  If you're stuck...</p>";
+		assert_eq!(Outcome::from_response(body), Some(Outcome::Incorrect));
+
+		let body = "<p>You gave an answer too recently; you have to wait after submitting an answer \
+			before trying again.  You have 4m 12s left to wait.</p>";
+		assert_eq!(Outcome::from_response(body), Some(Outcome::Wait("4m 12s".into())));
+		assert_eq!(parse_wait("4m 12s"), Some(Duration::seconds(252)));
+
+		assert_eq!(Outcome::from_response("<p>That's the right answer!</p>"), Some(Outcome::Correct));
+		assert_eq!(Outcome::from_response("<p>Nothing to see here</p>"), None);
+
+		// Whether the answer is right or not, for a part already solved or still locked
+		let body = "<article><p>You don't seem to be solving the right level.  \
+			Did you already complete it? <a href=\"/2025/day/1\">[Return to Day 1]</a></p></article>";
+		assert_eq!(Outcome::from_response(body), Some(Outcome::WrongLevel));
+	}
+
+	#[test]
+	fn test_submit() {
+
+		let mut ledger = Ledger::default();
+
+		let result = ledger.submit(1, 1, "100", |_| Ok(Outcome::TooHigh));
+		assert!(matches!(result, Err(AppError::Rejected { ref outcome, .. }) if outcome == "too high"));
+
+		ledger.submit(1, 1, "10", |_| Ok(Outcome::TooLow)).unwrap_err();
+		assert_eq!(ledger.bounds(1), (Some(10),Some(100)));
+		assert_eq!(ledger.bounds(2), (None,None));
+
+		// Known wrong answers are refused without contacting the server
+		let result = ledger.submit(1, 1, "100", |_| panic!("The answer should not be posted"));
+		assert!(matches!(result, Err(AppError::Rejected { .. })));
+
+		ledger.submit(1, 1, "42", |_| Ok(Outcome::Correct)).unwrap();
+		ledger.submit(1, 1, "42", |_| panic!("The answer should not be posted")).unwrap();
+		assert!(ledger.submit(1, 1, "43", |_| panic!("The answer should not be posted")).is_err());
+
+		assert_eq!(ledger.guesses(1).count(), 3);
+
+		// Waits are recorded, and observed
+		ledger.submit(1, 2, "7", |_| Ok(Outcome::Wait("1m".into()))).unwrap_err();
+		let result = ledger.submit(1, 2, "7", |_| panic!("The answer should not be posted"));
+		assert!(matches!(result, Err(AppError::RateLimited { .. })));
+		assert_eq!(ledger.lookup(2, "7"), None);
+
+		// Unchecked answers are not recorded, and do not block the real one
+		let mut ledger = Ledger::default();
+		assert!(ledger.submit(1, 2, "5", |_| Ok(Outcome::WrongLevel)).is_err());
+		assert_eq!(ledger.guesses(2).count(), 0);
+		ledger.submit(1, 2, "6", |_| Ok(Outcome::Correct)).unwrap();
+	}
+
+	#[test]
+	fn test_driver_cache() {
+
+		let cache = r#"{"parts":{"1":{
+			"10":{"submission_time":"2025-12-01T05:01:00Z","response":{"Err":"Incorrect"}},
+			"12":{"submission_time":"2025-12-01T05:03:00Z","response":{"Ok":null}}
+		}}}"#;
+
+		let ledger = Ledger::from_driver_cache(serde_json::from_str(cache).unwrap());
+
+		let answers:Vec<_> = ledger.guesses(1).map(|g| (g.answer.as_str(),g.outcome.clone())).collect();
+		assert_eq!(answers, vec![("10",Outcome::Incorrect),("12",Outcome::Correct)]);
+	}
+}
@@ -2,20 +2,15 @@
 
 pub const YEAR:i32 = 2025;
 
-#[cfg(test)]
 pub mod days;
+pub mod answers;
+pub mod bench;
+pub mod store;
+pub mod ledger;
 
-#[allow(unused)]
-#[derive(Debug)]
-pub enum AppError {
-	BadConfiguration(String),
-	IncorrectSolution(String)
-}
+mod error;
+pub use error::*;
 
 pub fn cookie() -> Result<String,AppError> {
-	std::env::var("COOKIE")
-		.map_err(|e| {
-			let msg = format!("Cookie error! {e:?}");
-			AppError::BadConfiguration(msg)
-		})
+	std::env::var("COOKIE").map_err(AppError::MissingCookie)
 }
@@ -1,32 +1,368 @@
-use std::process::ExitCode;
-use aoc_driver::aoc_magic;
+use std::{path::PathBuf, process::ExitCode, time::Instant};
+use aoc_2025::{AppError, cookie, bench::CountingAlloc, store::{FsInputStore, InputStore}};
+
+#[global_allocator]
+static ALLOC:CountingAlloc = CountingAlloc;
 
 pub use std::convert::identity;
 
-#[allow(unused)]
-#[derive(Debug)]
-pub enum AppError {
-	BadConfiguration(String),
-	IncorrectSolution(String)
+const USAGE:&str = "\
+Usage:
+	aoc_2025 setup
+	aoc_2025 list
+	aoc_2025 inputs
+	aoc_2025 fetch --day <DAY>
+	aoc_2025 import --day <DAY> --input <PATH>
+	aoc_2025 guesses --day <DAY> [--part <PART>]
+	aoc_2025 verify [--day <DAY>]
+	aoc_2025 bench [--day <DAY>] [--part <PART>] [--params <KEY=VALUE,...>] [--runs <N>] [--markdown]
+	aoc_2025 run --day <DAY> --part <PART> [--input <PATH>] [--params <KEY=VALUE,...>] [--explain] [--frames <DIR>]
+
+Inputs are kept under `$AOC_ROOT`, by default the project directory.";
+
+enum Command {
+	/// Creates `cache/` and `inputs/`
+	Setup,
+	/// Lists every registered solution
+	List,
+	/// Lists the stored inputs
+	Inputs,
+	/// Downloads and stores the input of a day
+	Fetch { day: i32 },
+	/// Stores an input obtained by other means
+	Import { day: i32, input: PathBuf },
+	/// Lists the answers submitted for a day, and the known bounds
+	Guesses { day: i32, part: Option<i32> },
+	/// Checks solutions against the known answers in `answers/`
+	Verify { day: Option<i32> },
+	/// Measures solutions on their stored inputs
+	Bench { day: Option<i32>, part: Option<i32>, params: Option<String>, runs: usize, markdown: bool },
+	/// Solves a puzzle for the given input (defaults to the stored one)
+	Run { day: i32, part: i32, input: Option<PathBuf>, params: Option<String>, explain: bool, frames: Option<PathBuf> },
 }
 
-pub fn cookie() -> Result<String,AppError> {
-	std::env::var("COOKIE")
-		.map_err(|e| {
-			let msg = format!("Cookie error! {e:?}");
-			AppError::BadConfiguration(msg)
-		})
+/// The options given to a command
+#[derive(Default)]
+struct Options {
+	day: Option<i32>,
+	part: Option<i32>,
+	input: Option<PathBuf>,
+	params: Option<String>,
+	runs: Option<i32>,
+	markdown: bool,
+	explain: bool,
+	frames: Option<PathBuf>,
 }
 
-fn main() -> ExitCode {
+fn parse_options(command:&str, mut args:impl Iterator<Item=String>, allowed:&[&str]) -> Result<Options,AppError> {
+
+	let bad = |msg:String| AppError::BadConfiguration(msg);
+
+	let mut options = Options::default();
+
+	while let Some(flag) = args.next() {
+
+		if !allowed.contains(&flag.as_str()) {
+			return Err(bad(format!("Unknown option `{flag}` for `{command}`")))
+		}
+
+		if flag == "--markdown" {
+			options.markdown = true;
+			continue;
+		}
+
+		if flag == "--explain" {
+			options.explain = true;
+			continue;
+		}
+
+		let value = args.next()
+			.ok_or_else(|| bad(format!("Missing value for `{flag}`")))?;
+
+		let number = |v:&str| v.parse::<i32>()
+			.map_err(|e| bad(format!("Invalid value for `{flag}`: {e}")));
+
+		match flag.as_str() {
+			"--day"    => options.day = Some(number(&value)?),
+			"--part"   => options.part = Some(number(&value)?),
+			"--input"  => options.input = Some(PathBuf::from(value)),
+			"--params" => options.params = Some(value),
+			"--runs"   => options.runs = Some(number(&value)?),
+			"--frames" => options.frames = Some(PathBuf::from(value)),
+			_ => unreachable!("Allowed options should be handled"),
+		}
+	}
+
+	Ok(options)
+}
+
+fn parse_args(mut args:impl Iterator<Item=String>) -> Result<Command,AppError> {
+
+	let missing = |flag:&str| AppError::BadConfiguration(format!("Missing `{flag}`"));
+
+	let Some(command) = args.next() else {
+		return Err(AppError::BadConfiguration("Missing command".into()))
+	};
+
+	let options = |allowed:&[&str]| parse_options(&command, args, allowed);
+
+	match command.as_str() {
+		"setup" => options(&[]).map(|_| Command::Setup),
+		"list" => options(&[]).map(|_| Command::List),
+		"inputs" => options(&[]).map(|_| Command::Inputs),
+		"fetch" => {
+			let o = options(&["--day"])?;
+			Ok(Command::Fetch { day: o.day.ok_or_else(|| missing("--day"))? })
+		},
+		"import" => {
+			let o = options(&["--day","--input"])?;
+			Ok(Command::Import {
+				day: o.day.ok_or_else(|| missing("--day"))?,
+				input: o.input.ok_or_else(|| missing("--input"))?,
+			})
+		},
+		"guesses" => {
+			let o = options(&["--day","--part"])?;
+			Ok(Command::Guesses { day: o.day.ok_or_else(|| missing("--day"))?, part: o.part })
+		},
+		"verify" => {
+			let o = options(&["--day"])?;
+			Ok(Command::Verify { day: o.day })
+		},
+		"bench" => {
+			let o = options(&["--day","--part","--params","--runs","--markdown"])?;
+			let runs = match o.runs.unwrap_or(10) {
+				r if r > 0 => r as usize,
+				_ => return Err(AppError::BadConfiguration("`--runs` should be positive".into())),
+			};
+			Ok(Command::Bench { day: o.day, part: o.part, params: o.params, runs, markdown: o.markdown })
+		},
+		"run" => {
+			let o = options(&["--day","--part","--input","--params","--explain","--frames"])?;
+			Ok(Command::Run {
+				day: o.day.ok_or_else(|| missing("--day"))?,
+				part: o.part.ok_or_else(|| missing("--part"))?,
+				input: o.input,
+				params: o.params,
+				explain: o.explain,
+				frames: o.frames,
+			})
+		},
+		other => Err(AppError::BadConfiguration(format!("Unknown command `{other}`"))),
+	}
+}
 
-	let cookie = cookie().unwrap();
+fn setup(store:&FsInputStore) -> Result<(),AppError> {
 
-	// Tests Creates `cache/` and `inputs/`
-	let _ = aoc_magic!(&cookie, 2024:1:1, |_| "");
+	cookie()?;
 
-	eprintln!("Project configured.");
+	store.init()?;
+
+	eprintln!("Project configured at {}", store.root().display());
 	eprintln!("Run `cargo test` to build and submit solutions");
 
-	ExitCode::FAILURE
+	Ok(())
+}
+
+fn inputs(store:&FsInputStore) -> Result<(),AppError> {
+
+	for day in store.list()? {
+		println!("day {day:>2}: {}", store.input_path(day).display());
+	}
+
+	Ok(())
+}
+
+fn fetch(store:&FsInputStore, day:i32) -> Result<(),AppError> {
+
+	store.fetch(day, &cookie()?)?;
+	eprintln!("Stored {}", store.input_path(day).display());
+
+	Ok(())
+}
+
+fn import(store:&FsInputStore, day:i32, path:PathBuf) -> Result<(),AppError> {
+
+	let input = std::fs::read_to_string(&path).map_err(AppError::io(&path))?;
+	store.import(day, &input)?;
+	eprintln!("Stored {}", store.input_path(day).display());
+
+	Ok(())
+}
+
+fn guesses(store:&FsInputStore, day:i32, part:Option<i32>) -> Result<(),AppError> {
+
+	let ledger = aoc_2025::ledger::Ledger::load(store, day)?;
+
+	for part in part.map_or(vec![1,2], |p| vec![p]) {
+
+		for guess in ledger.guesses(part) {
+			println!("day {day:>2} part {part}: {guess}");
+		}
+
+		match ledger.bounds(part) {
+			(None,None) => (),
+			(low,high) => println!(
+				"day {day:>2} part {part}: between {} and {}",
+				low.map_or("?".into(), |n| n.to_string()),
+				high.map_or("?".into(), |n| n.to_string()),
+			),
+		}
+	}
+
+	Ok(())
+}
+
+fn list() -> Result<(),AppError> {
+
+	for entry in aoc_2025::days::solutions() {
+		println!("day {:>2} part {}", entry.day, entry.part);
+	}
+
+	Ok(())
+}
+
+fn verify(store:&FsInputStore, day:Option<i32>) -> Result<(),AppError> {
+
+	use aoc_2025::answers::Verdict;
+
+	let mut mismatches = 0;
+
+	for entry in aoc_2025::days::solutions().filter(|e| day.is_none_or(|d| d == e.day)) {
+
+		let label = format!("day {:>2} part {}", entry.day, entry.part);
+
+		match aoc_2025::answers::verify(store, entry)? {
+			Verdict::Correct(answer) => println!("{label}: ok ({answer})"),
+			Verdict::Mismatch { expected, actual } => {
+				mismatches += 1;
+				println!("{label}: MISMATCH expected {expected}, got {actual}");
+			},
+			Verdict::Skipped => println!("{label}: skipped"),
+		}
+	}
+
+	if mismatches == 0 {
+		Ok(())
+	} else {
+		Err(AppError::Mismatch { count: mismatches })
+	}
+}
+
+fn bench(store:&FsInputStore, day:Option<i32>, part:Option<i32>, params:Option<String>, runs:usize, markdown:bool) -> Result<(),AppError> {
+
+	let mut stats = vec![];
+
+	let selected = |e:&&aoc_2025::days::Entry| day.is_none_or(|d| d == e.day) && part.is_none_or(|p| p == e.part);
+
+	for entry in aoc_2025::days::solutions().filter(selected) {
+
+		let Some(input) = store.get(entry.day)? else {
+			eprintln!("day {:>2} part {}: skipped, no stored input", entry.day, entry.part);
+			continue;
+		};
+
+		let s = match &params {
+			Some(params) => aoc_2025::bench::bench_with(entry, &input, params, runs)?,
+			None => aoc_2025::bench::bench(entry, &input, runs)?,
+		};
+
+		if !markdown {
+			println!(
+				"day {:>2} part {}: min {:.2?} median {:.2?} max {:.2?}, {} allocations ({} bytes)",
+				s.day, s.part, s.min, s.median, s.max, s.allocations, s.bytes
+			);
+		}
+
+		stats.push(s);
+	}
+
+	if markdown {
+		print!("{}", aoc_2025::bench::markdown(&stats));
+	}
+
+	Ok(())
+}
+
+fn run(store:&FsInputStore, day:i32, part:i32, input:Option<PathBuf>, params:Option<String>, explain:bool, frames:Option<PathBuf>) -> Result<(),AppError> {
+
+	let entry = aoc_2025::days::find(day, part)
+		.ok_or_else(|| AppError::BadConfiguration(format!("No solution for day {day} part {part}")))?;
+
+	let input = match input {
+		Some(path) => std::fs::read_to_string(&path).map_err(AppError::io(&path))?,
+		None => store.get(day)?.ok_or_else(|| {
+			AppError::BadConfiguration(format!("No stored input for day {day}, use `fetch` or `import` first"))
+		})?,
+	};
+
+	if explain {
+		match (entry.explain)(&input, params.as_deref().unwrap_or(""))? {
+			Some(explanation) => println!("{explanation}\n"),
+			None => eprintln!("Day {day} part {part} has no explanation"),
+		}
+	}
+
+	if let Some(dir) = frames {
+		match (entry.record)(&input, params.as_deref().unwrap_or(""), &dir)? {
+			true => eprintln!("Frames saved in {}", dir.display()),
+			false => eprintln!("Day {day} part {part} records no frames"),
+		}
+	}
+
+	let start = Instant::now();
+	let answer = match params {
+		Some(params) => (entry.solve_with)(&input, &params)?,
+		None => (entry.solve)(&input)?,
+	};
+	let elapsed = start.elapsed();
+
+	println!("{answer}");
+	eprintln!("Day {day} part {part} solved in {elapsed:?}");
+
+	Ok(())
+}
+
+/// Prints the error, followed by its chain of causes
+fn report(e:&dyn std::error::Error) {
+	eprintln!("{e}");
+	let mut source = e.source();
+	while let Some(cause) = source {
+		eprintln!("  caused by: {cause}");
+		source = cause.source();
+	}
+}
+
+fn main() -> ExitCode {
+
+	let command = match parse_args(std::env::args().skip(1)) {
+		Ok(command) => command,
+		Err(e) => {
+			report(&e);
+			eprintln!("{USAGE}");
+			return ExitCode::FAILURE
+		}
+	};
+
+	let store = FsInputStore::from_env();
+
+	let result = match command {
+		Command::Setup => setup(&store),
+		Command::List => list(),
+		Command::Inputs => inputs(&store),
+		Command::Fetch { day } => fetch(&store, day),
+		Command::Import { day, input } => import(&store, day, input),
+		Command::Guesses { day, part } => guesses(&store, day, part),
+		Command::Verify { day } => verify(&store, day),
+		Command::Bench { day, part, params, runs, markdown } => bench(&store, day, part, params, runs, markdown),
+		Command::Run { day, part, input, params, explain, frames } => run(&store, day, part, input, params, explain, frames),
+	};
+
+	match result {
+		Ok(()) => ExitCode::SUCCESS,
+		Err(e) => {
+			report(&e);
+			ExitCode::FAILURE
+		}
+	}
 }
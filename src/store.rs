@@ -0,0 +1,149 @@
+//! Puzzle inputs, kept locally so they are only downloaded once
+
+use std::path::{Path, PathBuf};
+
+use crate::{AppError, YEAR};
+
+/// Where puzzle inputs are kept
+pub trait InputStore {
+
+	/// The input of a day, if it is available locally
+	fn get(&self, day:i32) -> Result<Option<String>,AppError>;
+
+	/// The days with a locally available input, in order
+	fn list(&self) -> Result<Vec<i32>,AppError>;
+
+	/// Stores an input obtained by other means
+	fn import(&self, day:i32, input:&str) -> Result<(),AppError>;
+
+	/// Downloads the input of a day, and stores it
+	fn fetch(&self, day:i32, cookie:&str) -> Result<String,AppError> {
+		let input = aoc_driver::get_input(cookie, YEAR, day).map_err(|e| AppError::Network(Box::new(e)))?;
+		self.import(day, &input)?;
+		Ok(input)
+	}
+
+	/// The input of a day, downloading it if not available locally
+	fn get_or_fetch(&self, day:i32, cookie:&str) -> Result<String,AppError> {
+		match self.get(day)? {
+			Some(input) => Ok(input),
+			None => self.fetch(day, cookie),
+		}
+	}
+}
+
+/// Keeps inputs as `inputs/<DAY>.txt`, submission ledgers as `ledger/<DAY>.json`,
+/// and `aoc_driver` caches as `cache/<DAY>.json`, under a root directory.
+#[derive(Debug,Clone)]
+pub struct FsInputStore {
+	root: PathBuf
+}
+
+impl FsInputStore {
+
+	pub fn new(root:impl Into<PathBuf>) -> Self {
+		Self { root: root.into() }
+	}
+
+	/// The store rooted at `$AOC_ROOT`, or else at the project directory,
+	/// regardless of the working directory.
+	pub fn from_env() -> Self {
+		let root = std::env::var_os("AOC_ROOT")
+			.map(PathBuf::from)
+			.unwrap_or_else(|| env!("CARGO_MANIFEST_DIR").into());
+		Self::new(root)
+	}
+
+	pub fn root(&self) -> &Path {
+		&self.root
+	}
+
+	pub fn input_path(&self, day:i32) -> PathBuf {
+		self.root.join("inputs").join(format!("{day}.txt"))
+	}
+
+	pub fn cache_path(&self, day:i32) -> PathBuf {
+		self.root.join("cache").join(format!("{day}.json"))
+	}
+
+	pub fn ledger_path(&self, day:i32) -> PathBuf {
+		self.root.join("ledger").join(format!("{day}.json"))
+	}
+
+	/// Creates the `inputs/`, `ledger/` and `cache/` directories
+	pub fn init(&self) -> Result<(),AppError> {
+		for dir in ["inputs","ledger","cache"].map(|d| self.root.join(d)) {
+			std::fs::create_dir_all(&dir).map_err(AppError::io(dir))?;
+		}
+		Ok(())
+	}
+}
+
+impl InputStore for FsInputStore {
+
+	fn get(&self, day:i32) -> Result<Option<String>,AppError> {
+		let path = self.input_path(day);
+		match std::fs::read_to_string(&path) {
+			Ok(input) => Ok(Some(input)),
+			Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+			Err(e) => Err(AppError::io(path)(e)),
+		}
+	}
+
+	fn list(&self) -> Result<Vec<i32>,AppError> {
+
+		let dir = self.root.join("inputs");
+
+		if !dir.exists() {
+			return Ok(vec![])
+		}
+
+		let mut days = vec![];
+
+		for entry in std::fs::read_dir(&dir).map_err(AppError::io(&dir))? {
+			let path = entry.map_err(AppError::io(&dir))?.path();
+			let is_txt = path.extension().is_some_and(|ext| ext == "txt");
+			let day = path.file_stem().and_then(|s| s.to_str()?.parse().ok());
+			if let (true, Some(day)) = (is_txt, day) {
+				days.push(day);
+			}
+		}
+
+		days.sort_unstable();
+
+		Ok(days)
+	}
+
+	fn import(&self, day:i32, input:&str) -> Result<(),AppError> {
+		self.init()?;
+		let path = self.input_path(day);
+		std::fs::write(&path, input).map_err(AppError::io(path))
+	}
+}
+
+#[cfg(test)]
+mod test {
+
+	use super::*;
+
+	#[test]
+	fn test_fs_input_store() {
+
+		let root = std::env::temp_dir().join(format!("aoc_2025_store_{}", std::process::id()));
+		let store = FsInputStore::new(&root);
+
+		assert!(store.list().unwrap().is_empty());
+		assert_eq!(store.get(7).unwrap(), None);
+
+		store.import(7, "S.^").unwrap();
+		store.import(10, "[.#]").unwrap();
+
+		assert_eq!(store.list().unwrap(), vec![7,10]);
+		assert_eq!(store.get(7).unwrap().as_deref(), Some("S.^"));
+
+		// Available locally, so no cookie is needed
+		assert_eq!(store.get_or_fetch(10, "").unwrap(), "[.#]");
+
+		std::fs::remove_dir_all(root).unwrap();
+	}
+}